@@ -1,4 +1,5 @@
-use clap::{Parser, Subcommand};
+use clap::{Parser, Subcommand, ValueEnum};
+use std::path::{Path, PathBuf};
 
 #[derive(Parser)]
 #[command(name = "termtask")]
@@ -12,7 +13,7 @@ pub struct Cli {
     #[arg(long, global = true, help = "Force global todo storage instead of project-specific")]
     pub global: bool,
 
-    #[arg(long, value_name = "PATH", help = "Path to custom todo.json file")]
+    #[arg(long, global = true, value_name = "PATH", help = "Path to custom todo.json file")]
     pub file: Option<String>,
 }
 
@@ -22,6 +23,172 @@ pub enum Commands {
     Init {
         #[arg(long, help = "Create personal todos (.todo.json) instead of shared (todo.json)")]
         personal: bool,
+
+        #[arg(long, help = "Register the TermTask merge driver for todo.json in .gitattributes and git config")]
+        merge_driver: bool,
+    },
+
+    #[command(about = "Add a todo without opening the interface and print its id")]
+    Add {
+        #[arg(value_name = "TITLE", required = true, num_args = 1.., help = "Title of the new todo")]
+        title: Vec<String>,
+
+        #[arg(long, value_name = "TEXT", conflicts_with_all = ["desc_file", "stdin"], help = "Description (markdown)")]
+        desc: Option<String>,
+
+        #[arg(long, value_name = "PATH", conflicts_with = "stdin", help = "Read the description from a file")]
+        desc_file: Option<PathBuf>,
+
+        #[arg(long, help = "Read the description from standard input")]
+        stdin: bool,
+    },
+
+    #[command(about = "Print todos, optionally filtered, as a table or for other programs")]
+    List {
+        #[arg(long, conflicts_with_all = ["active", "all"], help = "Only completed todos")]
+        completed: bool,
+
+        #[arg(long, conflicts_with = "all", help = "Only open todos (the default)")]
+        active: bool,
+
+        #[arg(long, help = "Open and completed todos")]
+        all: bool,
+
+        #[arg(long, value_name = "TEXT", help = "Only todos whose title, description or tags contain TEXT")]
+        search: Option<String>,
+
+        #[arg(long, value_name = "TAG", help = "Only todos with this tag")]
+        tag: Option<String>,
+
+        #[arg(long, value_name = "WHEN", help = "Only todos changed since WHEN (2024-03-01, yesterday, 7d, 12h)")]
+        since: Option<String>,
+
+        #[arg(long, value_enum, default_value_t = ListFormat::Table, help = "Output format")]
+        format: ListFormat,
+    },
+
+    #[command(about = "Mark todos as completed")]
+    Done {
+        #[arg(value_name = "TODO", required = true, num_args = 1.., help = "Index from `termtask list` (or `#3`) or id prefix (or `id:1234`)")]
+        todos: Vec<String>,
+    },
+
+    #[command(about = "Mark completed todos as open again")]
+    Reopen {
+        #[arg(value_name = "TODO", required = true, num_args = 1.., help = "Index from `termtask list` (or `#3`) or id prefix (or `id:1234`)")]
+        todos: Vec<String>,
+    },
+
+    #[command(about = "Delete todos (their subtasks move up a level)")]
+    Rm {
+        #[arg(value_name = "TODO", required = true, num_args = 1.., help = "Index from `termtask list` (or `#3`) or id prefix (or `id:1234`)")]
+        todos: Vec<String>,
+    },
+
+    #[command(about = "Edit a todo's title and description in $EDITOR")]
+    Edit {
+        #[arg(value_name = "TODO", help = "Index from `termtask list` (or `#3`) or id prefix (or `id:1234`)")]
+        todo: String,
+    },
+
+    #[command(about = "Sync TODO, FIXME and HACK comments from the repository's code into the project todos")]
+    Scan {
+        #[arg(long, help = "Print the comments that were found without changing the todo file")]
+        dry_run: bool,
+    },
+
+    #[command(about = "Git hooks that complete todos named as `Closes todo:<id>` in commit messages")]
+    Hook {
+        #[command(subcommand)]
+        action: HookAction,
+    },
+
+    #[command(about = "List backups of the todo file and restore one")]
+    Restore {
+        #[arg(value_name = "NUMBER", help = "Backup to restore, as numbered in the list (prompts when omitted)")]
+        backup: Option<usize>,
+    },
+
+    #[command(about = "Import todos from another tool's file into the todo file")]
+    Import {
+        #[arg(value_name = "FILE", help = "File to import")]
+        input: PathBuf,
+
+        #[arg(long, value_enum, help = "Format of the file (guessed from the extension when omitted)")]
+        format: Option<ExchangeFormat>,
+    },
+
+    #[command(about = "Export the todos in another tool's format")]
+    Export {
+        #[arg(long, value_enum, help = "Format to write")]
+        format: ExchangeFormat,
+
+        #[arg(short, long, value_name = "PATH", help = "Write to a file instead of stdout")]
+        output: Option<PathBuf>,
+    },
+
+    #[command(about = "Copy the todos into a new file, converting the format (e.g. todos.json to todos.db)")]
+    Migrate {
+        #[arg(value_name = "TARGET", help = "File to create; the format is chosen by its extension (.json, .toml, .md, .db)")]
+        target: PathBuf,
+    },
+
+    #[command(about = "Three-way merge of todo files, for use as a git merge driver")]
+    MergeDriver {
+        #[arg(value_name = "BASE", help = "Common ancestor version (%O)")]
+        base: PathBuf,
+
+        #[arg(value_name = "OURS", help = "Current branch version, overwritten with the result (%A)")]
+        ours: PathBuf,
+
+        #[arg(value_name = "THEIRS", help = "Other branch version (%B)")]
+        theirs: PathBuf,
     },
 }
 
+#[derive(Subcommand)]
+pub enum HookAction {
+    #[command(about = "Install the commit-msg and post-commit hooks in the current repository")]
+    Install,
+
+    #[command(hide = true)]
+    CommitMsg {
+        #[arg(value_name = "MESSAGE_FILE")]
+        message_file: PathBuf,
+    },
+
+    #[command(hide = true)]
+    PostCommit,
+}
+
+#[derive(Clone, Copy, ValueEnum)]
+pub enum ListFormat {
+    Table,
+    Plain,
+    Json,
+    Ndjson,
+    Markdown,
+}
+
+#[derive(Clone, Copy, ValueEnum)]
+pub enum ExchangeFormat {
+    #[value(name = "todotxt", alias = "todo.txt", help = "todo.txt lines")]
+    Todotxt,
+
+    #[value(name = "ics", alias = "ical", help = "iCalendar VTODO components")]
+    Ics,
+
+    #[value(name = "taskwarrior", alias = "task", help = "Taskwarrior `task export` JSON")]
+    Taskwarrior,
+}
+
+impl ExchangeFormat {
+    pub fn from_path(path: &Path) -> Option<Self> {
+        let extension = path.extension()?.to_str()?.to_ascii_lowercase();
+        match extension.as_str() {
+            "txt" => Some(ExchangeFormat::Todotxt),
+            "ics" | "ical" => Some(ExchangeFormat::Ics),
+            _ => None,
+        }
+    }
+}
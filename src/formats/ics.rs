@@ -0,0 +1,481 @@
+use crate::models::{Priority, TodoItem, TodoList};
+use chrono::{DateTime, Local, NaiveDate, NaiveDateTime, NaiveTime, TimeZone, Utc};
+use chrono_tz::Tz;
+use serde_json::{json, Value};
+use std::collections::HashMap;
+use uuid::Uuid;
+
+pub const EXTENSION_KEY: &str = "ics";
+const PRODUCT_ID: &str = "-//TermTask//TermTask//EN";
+const DATE_FORMAT: &str = "%Y%m%d";
+const DATE_TIME_FORMAT: &str = "%Y%m%dT%H%M%S";
+const LINE_LIMIT: usize = 75;
+
+struct Property {
+    name: String,
+    params: Vec<(String, String)>,
+    value: String,
+    raw: String,
+}
+
+impl Property {
+    fn param(&self, name: &str) -> Option<&str> {
+        self.params.iter()
+            .find(|(key, _)| key.eq_ignore_ascii_case(name))
+            .map(|(_, value)| value.as_str())
+    }
+}
+
+pub fn parse(content: &str) -> Vec<TodoItem> {
+    let mut items = Vec::new();
+    let mut properties: Option<Vec<Property>> = None;
+    let mut depth = 0;
+
+    for line in unfold(content) {
+        let Some(property) = parse_property(&line) else { continue };
+        let is_vtodo = property.value.eq_ignore_ascii_case("VTODO");
+
+        match (property.name.as_str(), properties.as_mut()) {
+            ("BEGIN", None) if is_vtodo => {
+                properties = Some(Vec::new());
+                depth = 0;
+            }
+            ("END", Some(_)) if is_vtodo && depth == 0 => {
+                if let Some(item) = properties.take().and_then(|properties| item_from_properties(&properties)) {
+                    items.push(item);
+                }
+            }
+            (name, Some(properties)) => {
+                match name {
+                    "BEGIN" => depth += 1,
+                    "END" => depth -= 1,
+                    _ => {}
+                }
+                properties.push(property);
+            }
+            _ => {}
+        }
+    }
+
+    let ids: HashMap<String, Uuid> = items.iter().map(|(item, _)| (export_uid(item), item.id)).collect();
+    items.into_iter()
+        .map(|(mut item, parent_uid)| {
+            item.parent_id = parent_uid.and_then(|uid| ids.get(&uid).copied().or_else(|| Uuid::parse_str(&uid).ok()));
+            item
+        })
+        .collect()
+}
+
+pub fn write(todo_list: &TodoList) -> String {
+    let mut items: Vec<&TodoItem> = todo_list.items.iter().collect();
+    items.sort_by_key(|item| item.order);
+
+    let mut lines = vec![
+        "BEGIN:VCALENDAR".to_string(),
+        "VERSION:2.0".to_string(),
+        format!("PRODID:{}", PRODUCT_ID),
+    ];
+    let stamp = format_date_time(&Utc::now());
+    for item in items {
+        lines.extend(item_lines(todo_list, item, &stamp));
+    }
+    lines.push("END:VCALENDAR".to_string());
+
+    lines.iter().map(|line| fold(line)).collect()
+}
+
+fn item_lines(todo_list: &TodoList, item: &TodoItem, stamp: &str) -> Vec<String> {
+    let extension = item.extensions.get(EXTENSION_KEY);
+    let uid = export_uid(item);
+
+    let status = if item.is_completed() {
+        "COMPLETED"
+    } else if item.status != todo_list.initial_state() {
+        "IN-PROCESS"
+    } else {
+        "NEEDS-ACTION"
+    };
+
+    let mut lines = vec![
+        "BEGIN:VTODO".to_string(),
+        format!("UID:{}", escape(&uid)),
+        format!("DTSTAMP:{}", stamp),
+        format!("CREATED:{}", format_date_time(&item.created_at)),
+        format!("SUMMARY:{}", escape(&item.title)),
+    ];
+
+    if let Some(description) = item.description.as_deref().filter(|description| !description.trim().is_empty()) {
+        lines.push(format!("DESCRIPTION:{}", escape(description)));
+    }
+    lines.push(format!("STATUS:{}", status));
+    if let Some(completed_at) = &item.completed_at {
+        lines.push(format!("COMPLETED:{}", format_date_time(completed_at)));
+    }
+    if let Some(updated_at) = &item.updated_at {
+        lines.push(format!("LAST-MODIFIED:{}", format_date_time(updated_at)));
+    }
+    if let Some(due_at) = &item.due_at {
+        let local = due_at.with_timezone(&Local);
+        if local.time() == NaiveTime::MIN {
+            lines.push(format!("DUE;VALUE=DATE:{}", local.format(DATE_FORMAT)));
+        } else {
+            lines.push(format!("DUE:{}", format_date_time(due_at)));
+        }
+    }
+    if let Some(priority) = ics_priority(item.priority) {
+        lines.push(format!("PRIORITY:{}", priority));
+    }
+    if !item.tags.is_empty() {
+        let tags: Vec<String> = item.tags.iter().map(|tag| escape(tag)).collect();
+        lines.push(format!("CATEGORIES:{}", tags.join(",")));
+    }
+    if let Some(parent_id) = &item.parent_id {
+        let parent_uid = todo_list.get_todo_by_id(parent_id).map_or_else(|| parent_id.to_string(), export_uid);
+        lines.push(format!("RELATED-TO:{}", escape(&parent_uid)));
+    }
+
+    let preserved = extension.and_then(|extension| extension.get("properties")).and_then(Value::as_array);
+    lines.extend(preserved.into_iter().flatten().filter_map(Value::as_str).map(str::to_string));
+
+    lines.push("END:VTODO".to_string());
+    lines
+}
+
+/// The UID a todo is written under: the one it was imported with, or its id.
+fn export_uid(item: &TodoItem) -> String {
+    item.extensions.get(EXTENSION_KEY)
+        .and_then(|extension| extension.get("uid"))
+        .and_then(Value::as_str)
+        .map_or_else(|| item.id.to_string(), str::to_string)
+}
+
+/// The todo and the UID of its parent, which is resolved once the whole file is read.
+fn item_from_properties(properties: &[Property]) -> Option<(TodoItem, Option<String>)> {
+    let mut item = TodoItem::new(String::new(), None);
+    item.status = String::new();
+    let mut extension = serde_json::Map::new();
+    let mut preserved = Vec::new();
+    let mut status = None;
+    let mut completed_at = None;
+    let mut parent_uid = None;
+    let mut depth = 0;
+
+    for property in properties {
+        if depth > 0 || property.name == "BEGIN" {
+            match property.name.as_str() {
+                "BEGIN" => depth += 1,
+                "END" => depth -= 1,
+                _ => {}
+            }
+            preserved.push(property.raw.clone());
+            continue;
+        }
+
+        match property.name.as_str() {
+            "UID" => {
+                let uid = unescape(&property.value);
+                match Uuid::parse_str(&uid) {
+                    Ok(id) => item.id = id,
+                    Err(_) => {
+                        extension.insert("uid".to_string(), json!(uid));
+                    }
+                }
+            }
+            "SUMMARY" => item.title = unescape(&property.value),
+            "DESCRIPTION" => item.description = Some(unescape(&property.value)).filter(|description| !description.is_empty()),
+            "STATUS" => status = Some(property.value.to_ascii_uppercase()),
+            "COMPLETED" => completed_at = parse_date_time(property),
+            "CREATED" => {
+                if let Some(created_at) = parse_date_time(property) {
+                    item.created_at = created_at;
+                }
+            }
+            "LAST-MODIFIED" => item.updated_at = parse_date_time(property),
+            "DUE" => item.due_at = parse_date_time(property),
+            "PRIORITY" => item.priority = property.value.trim().parse().map_or(Priority::None, priority_from_ics),
+            "CATEGORIES" => {
+                for tag in split_list(&property.value) {
+                    if !tag.is_empty() && !item.has_tag(&tag) {
+                        item.tags.push(tag);
+                    }
+                }
+            }
+            "RELATED-TO" if property.param("RELTYPE").is_none_or(|reltype| reltype.eq_ignore_ascii_case("PARENT")) => {
+                parent_uid = Some(unescape(&property.value));
+            }
+            "DTSTAMP" => {}
+            _ => preserved.push(property.raw.clone()),
+        }
+    }
+
+    match status.as_deref() {
+        Some("COMPLETED" | "CANCELLED") => item.completed_at = Some(completed_at.unwrap_or_else(Utc::now)),
+        Some("IN-PROCESS") => item.status = "In Progress".to_string(),
+        _ if completed_at.is_some() => item.completed_at = completed_at,
+        _ => {}
+    }
+
+    if !preserved.is_empty() {
+        extension.insert("properties".to_string(), json!(preserved));
+    }
+    if !extension.is_empty() {
+        item.extensions.insert(EXTENSION_KEY.to_string(), Value::Object(extension));
+    }
+
+    (!item.title.is_empty()).then_some((item, parent_uid))
+}
+
+fn unfold(content: &str) -> Vec<String> {
+    let mut lines: Vec<String> = Vec::new();
+    for line in content.lines() {
+        match (line.strip_prefix([' ', '\t']), lines.last_mut()) {
+            (Some(continuation), Some(previous)) => previous.push_str(continuation),
+            _ => lines.push(line.to_string()),
+        }
+    }
+    lines
+}
+
+fn fold(line: &str) -> String {
+    let mut output = String::new();
+    let mut length = 0;
+    for c in line.chars() {
+        if length + c.len_utf8() > LINE_LIMIT {
+            output.push_str("\r\n ");
+            length = 1;
+        }
+        output.push(c);
+        length += c.len_utf8();
+    }
+    output.push_str("\r\n");
+    output
+}
+
+fn parse_property(line: &str) -> Option<Property> {
+    let mut in_quotes = false;
+    let split = line.char_indices().find(|(_, c)| {
+        if *c == '"' {
+            in_quotes = !in_quotes;
+        }
+        *c == ':' && !in_quotes
+    })?.0;
+
+    let (head, value) = (&line[..split], &line[split + 1..]);
+    let mut parts = head.split(';');
+    let name = parts.next()?.trim().to_ascii_uppercase();
+    let params = parts
+        .filter_map(|param| param.split_once('='))
+        .map(|(key, value)| (key.to_ascii_uppercase(), value.trim_matches('"').to_string()))
+        .collect();
+
+    Some(Property { name, params, value: value.to_string(), raw: line.to_string() })
+}
+
+fn parse_date_time(property: &Property) -> Option<DateTime<Utc>> {
+    let value = property.value.trim();
+    if property.param("VALUE").is_some_and(|kind| kind.eq_ignore_ascii_case("DATE")) || value.len() == 8 {
+        let date = NaiveDate::parse_from_str(value, DATE_FORMAT).ok()?;
+        return local_to_utc(date.and_time(NaiveTime::MIN));
+    }
+
+    if let Some(utc) = value.strip_suffix('Z') {
+        return NaiveDateTime::parse_from_str(utc, DATE_TIME_FORMAT).ok().map(|naive| naive.and_utc());
+    }
+
+    // Zones that aren't IANA names (e.g. Outlook's "W. Europe Standard Time") are read as local time.
+    let naive = NaiveDateTime::parse_from_str(value, DATE_TIME_FORMAT).ok()?;
+    match property.param("TZID").and_then(|tzid| tzid.trim_start_matches('/').parse::<Tz>().ok()) {
+        Some(zone) => zone.from_local_datetime(&naive).earliest().map(|time| time.with_timezone(&Utc)),
+        None => local_to_utc(naive),
+    }
+}
+
+fn local_to_utc(naive: NaiveDateTime) -> Option<DateTime<Utc>> {
+    Local.from_local_datetime(&naive).earliest().map(|local| local.with_timezone(&Utc))
+}
+
+fn format_date_time(date: &DateTime<Utc>) -> String {
+    format!("{}Z", date.format(DATE_TIME_FORMAT))
+}
+
+fn ics_priority(priority: Priority) -> Option<u8> {
+    match priority {
+        Priority::None => None,
+        Priority::Urgent => Some(1),
+        Priority::High => Some(3),
+        Priority::Medium => Some(5),
+        Priority::Low => Some(9),
+    }
+}
+
+fn priority_from_ics(priority: u8) -> Priority {
+    match priority {
+        0 => Priority::None,
+        1 | 2 => Priority::Urgent,
+        3 | 4 => Priority::High,
+        5 => Priority::Medium,
+        _ => Priority::Low,
+    }
+}
+
+fn escape(text: &str) -> String {
+    text.replace('\\', "\\\\")
+        .replace(';', "\\;")
+        .replace(',', "\\,")
+        .replace("\r\n", "\\n")
+        .replace('\n', "\\n")
+}
+
+fn unescape(text: &str) -> String {
+    let mut output = String::new();
+    let mut chars = text.chars();
+    while let Some(c) = chars.next() {
+        if c != '\\' {
+            output.push(c);
+            continue;
+        }
+        match chars.next() {
+            Some('n' | 'N') => output.push('\n'),
+            Some(escaped) => output.push(escaped),
+            None => output.push('\\'),
+        }
+    }
+    output
+}
+
+fn split_list(value: &str) -> Vec<String> {
+    let mut values = Vec::new();
+    let mut current = String::new();
+    let mut chars = value.chars();
+    while let Some(c) = chars.next() {
+        match c {
+            '\\' => {
+                current.push(c);
+                if let Some(escaped) = chars.next() {
+                    current.push(escaped);
+                }
+            }
+            ',' => values.push(unescape(std::mem::take(&mut current).trim())),
+            _ => current.push(c),
+        }
+    }
+    values.push(unescape(current.trim()));
+    values
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::Duration;
+
+    const CALENDAR: &str = "BEGIN:VCALENDAR\r
+VERSION:2.0\r
+BEGIN:VTODO\r
+UID:outside-uid@example.com\r
+SUMMARY:Pay rent\\, electricity\r
+DESCRIPTION:Line one\\nLine two\r
+STATUS:IN-PROCESS\r
+DUE;TZID=America/New_York:20240305T170000\r
+PRIORITY:1\r
+CATEGORIES:home,bills\r
+X-APPLE-SORT-ORDER:7\r
+BEGIN:VALARM\r
+ACTION:DISPLAY\r
+END:VALARM\r
+END:VTODO\r
+END:VCALENDAR\r
+";
+
+    fn property(line: &str) -> Property {
+        parse_property(line).unwrap()
+    }
+
+    #[test]
+    fn reads_todos() {
+        let items = parse(CALENDAR);
+        assert_eq!(items.len(), 1);
+
+        let item = &items[0];
+        assert_eq!(item.title, "Pay rent, electricity");
+        assert_eq!(item.description.as_deref(), Some("Line one\nLine two"));
+        assert_eq!(item.status, "In Progress");
+        assert_eq!(item.priority, Priority::Urgent);
+        assert_eq!(item.tags, vec!["home".to_string(), "bills".to_string()]);
+        assert_eq!(item.due_at, Some(Utc.with_ymd_and_hms(2024, 3, 5, 22, 0, 0).unwrap()));
+    }
+
+    #[test]
+    fn applies_time_zones() {
+        let expected = Utc.with_ymd_and_hms(2024, 7, 1, 7, 30, 0).unwrap();
+        assert_eq!(parse_date_time(&property("DUE;TZID=Europe/Berlin:20240701T093000")), Some(expected));
+        assert_eq!(parse_date_time(&property("DUE;TZID=\"/Europe/Berlin\":20240701T093000")), Some(expected));
+        assert_eq!(parse_date_time(&property("DUE:20240701T073000Z")), Some(expected));
+
+        let naive = NaiveDate::from_ymd_opt(2024, 7, 1).unwrap().and_hms_opt(9, 30, 0).unwrap();
+        assert_eq!(parse_date_time(&property("DUE;TZID=Somewhere Standard Time:20240701T093000")), local_to_utc(naive));
+        assert_eq!(parse_date_time(&property("DUE:20240701T093000")), local_to_utc(naive));
+    }
+
+    #[test]
+    fn links_children_to_parents_by_uid() {
+        let mut todo_list = TodoList::new();
+        let parent = todo_list.add_todo("Parent".to_string(), None);
+        let child = todo_list.add_todo("Child".to_string(), None);
+        let orphan = todo_list.add_todo("Orphan".to_string(), None);
+        todo_list.items[1].parent_id = Some(parent);
+        let missing = Uuid::new_v4();
+        todo_list.items[2].parent_id = Some(missing);
+
+        let items = parse(&write(&todo_list));
+        assert_eq!(items[0].id, parent);
+        assert_eq!((items[1].id, items[1].parent_id), (child, Some(parent)));
+        assert_eq!((items[2].id, items[2].parent_id), (orphan, Some(missing)));
+    }
+
+    #[test]
+    fn importing_again_keeps_links_to_parents_with_a_uid() {
+        let calendar = CALENDAR.replace(
+            "END:VCALENDAR",
+            "BEGIN:VTODO\r\nUID:child-uid@example.com\r\nSUMMARY:Find the bill\r\nRELATED-TO:outside-uid@example.com\r\nEND:VTODO\r\nEND:VCALENDAR",
+        );
+        let mut todo_list = TodoList::new();
+        crate::formats::import_items(&mut todo_list, parse(&calendar));
+        let summary = crate::formats::import_items(&mut todo_list, parse(&calendar));
+
+        assert_eq!(summary.added, 0);
+        assert_eq!(todo_list.items.len(), 2);
+        assert_eq!(todo_list.items[1].parent_id, Some(todo_list.items[0].id));
+    }
+
+    #[test]
+    fn round_trips_todos() {
+        let mut todo_list = TodoList::new();
+        todo_list.items = parse(CALENDAR);
+        todo_list.normalize_statuses();
+        todo_list.normalize_order();
+        todo_list.add_todo("Child with a long title that needs folding across several lines of output".to_string(), None);
+        todo_list.items[1].parent_id = Some(todo_list.items[0].id);
+        todo_list.items[1].due_at = local_to_utc(NaiveDate::from_ymd_opt(2024, 3, 9).unwrap().and_time(NaiveTime::MIN));
+        todo_list.items[1].completed_at = Some(todo_list.items[1].created_at + Duration::hours(1));
+
+        let output = write(&todo_list);
+        assert!(output.lines().all(|line| line.len() <= LINE_LIMIT + 1));
+        assert!(output.contains("X-APPLE-SORT-ORDER:7\r\nBEGIN:VALARM\r\nACTION:DISPLAY\r\nEND:VALARM\r\n"));
+        assert!(output.contains("UID:outside-uid@example.com\r\n"));
+        assert!(output.contains("DUE;VALUE=DATE:20240309\r\n"));
+        assert!(output.contains("RELATED-TO:outside-uid@example.com\r\n"));
+
+        let items = parse(&output);
+        assert_eq!(items.len(), 2);
+        assert_eq!(items[1].parent_id, Some(items[0].id));
+        for (read, written) in items.iter().zip(&todo_list.items) {
+            assert_eq!(read.title, written.title);
+            assert_eq!(read.description, written.description);
+            assert_eq!(read.due_at, written.due_at);
+            assert_eq!(read.priority, written.priority);
+            assert_eq!(read.tags, written.tags);
+            assert_eq!(read.extensions, written.extensions);
+            assert_eq!(read.is_completed(), written.is_completed());
+        }
+    }
+}
@@ -0,0 +1,257 @@
+use crate::models::{Priority, TodoItem};
+use crate::storage::markdown_store::escape_title;
+use anyhow::{bail, Context, Result};
+use chrono::{DateTime, Local, Utc};
+use serde::Serialize;
+use std::path::Path;
+use uuid::Uuid;
+
+pub const SCHEMA_VERSION: u32 = 1;
+const SHORT_ID_LENGTH: usize = 8;
+
+#[derive(Serialize)]
+pub struct TodoRecord<'a> {
+    pub index: usize,
+    pub id: Uuid,
+    pub title: &'a str,
+    pub description: Option<&'a str>,
+    pub status: &'a str,
+    pub completed: bool,
+    pub priority: Option<&'static str>,
+    pub tags: &'a [String],
+    pub parent_id: Option<Uuid>,
+    pub created_at: DateTime<Utc>,
+    pub updated_at: DateTime<Utc>,
+    pub completed_at: Option<DateTime<Utc>>,
+    pub due_at: Option<DateTime<Utc>>,
+}
+
+impl<'a> TodoRecord<'a> {
+    pub fn new(item: &'a TodoItem) -> Self {
+        Self {
+            index: index(item),
+            id: item.id,
+            title: &item.title,
+            description: item.description.as_deref().filter(|description| !description.trim().is_empty()),
+            status: &item.status,
+            completed: item.is_completed(),
+            priority: priority_name(item.priority),
+            tags: &item.tags,
+            parent_id: item.parent_id,
+            created_at: item.created_at,
+            updated_at: item.last_changed(),
+            completed_at: item.completed_at,
+            due_at: item.due_at,
+        }
+    }
+}
+
+#[derive(Serialize)]
+struct ListDocument<'a> {
+    schema_version: u32,
+    file: &'a Path,
+    todos: Vec<TodoRecord<'a>>,
+}
+
+pub fn index(item: &TodoItem) -> usize {
+    item.order as usize + 1
+}
+
+pub fn short_id(item: &TodoItem) -> String {
+    item.id.to_string()[..SHORT_ID_LENGTH].to_string()
+}
+
+pub fn json(items: &[TodoItem], file: &Path) -> Result<String> {
+    let document = ListDocument {
+        schema_version: SCHEMA_VERSION,
+        file,
+        todos: items.iter().map(TodoRecord::new).collect(),
+    };
+    Ok(serde_json::to_string_pretty(&document)? + "\n")
+}
+
+pub fn ndjson(items: &[TodoItem]) -> Result<String> {
+    items.iter()
+        .map(|item| Ok(serde_json::to_string(&TodoRecord::new(item))? + "\n"))
+        .collect()
+}
+
+pub fn plain(items: &[TodoItem]) -> String {
+    items.iter()
+        .map(|item| format!("{}\t{}\t{}\n", index(item), short_id(item), item.title))
+        .collect()
+}
+
+pub fn markdown(items: &[TodoItem]) -> String {
+    items.iter()
+        .map(|item| {
+            let checkbox = if item.is_completed() { "x" } else { " " };
+            let tags: String = item.tags.iter().map(|tag| format!(" #{}", tag.replace(' ', "-"))).collect();
+            format!("- [{}] {}{}\n", checkbox, markdown_title(&item.title), tags)
+        })
+        .collect()
+}
+
+/// The title on one line, with trailing `#words` escaped as the Markdown store does and a
+/// leading heading, list or checkbox marker escaped so it stays part of the item's text.
+fn markdown_title(title: &str) -> String {
+    let title = escape_title(&title.trim().replace(['\r', '\n'], " "));
+    if title.starts_with(['#', '*', '-', '+', '>', '[']) {
+        format!("\\{}", title)
+    } else {
+        title
+    }
+}
+
+pub fn table(items: &[TodoItem]) -> String {
+    let header = ["#", "ID", "STATUS", "PRIORITY", "DUE", "TITLE"].map(str::to_string);
+    let rows: Vec<[String; 6]> = items.iter()
+        .map(|item| {
+            let tags: String = item.tags.iter().map(|tag| format!(" #{}", tag)).collect();
+            [
+                index(item).to_string(),
+                short_id(item),
+                item.status.clone(),
+                priority_name(item.priority).unwrap_or("").to_string(),
+                item.due_at.map_or(String::new(), |due_at| due_at.with_timezone(&Local).format("%Y-%m-%d").to_string()),
+                format!("{}{}", item.title, tags),
+            ]
+        })
+        .collect();
+
+    let mut widths = header.clone().map(|column| column.chars().count());
+    for row in &rows {
+        for (width, cell) in widths.iter_mut().zip(row) {
+            *width = (*width).max(cell.chars().count());
+        }
+    }
+
+    std::iter::once(&header).chain(rows.iter())
+        .map(|row| {
+            let cells: Vec<String> = row.iter().zip(widths)
+                .map(|(cell, width)| format!("{:<width$}", cell, width = width))
+                .collect();
+            cells.join("  ").trim_end().to_string() + "\n"
+        })
+        .collect()
+}
+
+fn priority_name(priority: Priority) -> Option<&'static str> {
+    match priority {
+        Priority::None => None,
+        Priority::Low => Some("low"),
+        Priority::Medium => Some("medium"),
+        Priority::High => Some("high"),
+        Priority::Urgent => Some("urgent"),
+    }
+}
+
+pub fn find<'a>(items: &'a [TodoItem], reference: &str) -> Result<&'a TodoItem> {
+    let reference = reference.trim().to_ascii_lowercase();
+    if reference.is_empty() {
+        bail!("Give a todo index or id prefix");
+    }
+
+    if let Some(position) = reference.strip_prefix('#') {
+        return position.parse::<usize>().ok()
+            .and_then(|position| items.iter().find(|item| index(item) == position))
+            .with_context(|| format!("No todo has index {}. Run `termtask list --all` to see them", position));
+    }
+
+    let reference = match reference.strip_prefix("id:") {
+        Some(prefix) => prefix.to_string(),
+        None => {
+            if let Ok(position) = reference.parse::<usize>() {
+                if let Some(item) = items.iter().find(|item| index(item) == position) {
+                    return Ok(item);
+                }
+            }
+            reference
+        }
+    };
+    if reference.is_empty() {
+        bail!("Give an id prefix after \"id:\"");
+    }
+
+    let matches: Vec<&TodoItem> = items.iter()
+        .filter(|item| item.id.to_string().starts_with(&reference))
+        .collect();
+
+    match matches.as_slice() {
+        [item] => Ok(item),
+        [] => bail!("No todo has index or id prefix \"{}\". Run `termtask list --all` to see them", reference),
+        candidates => {
+            let lines: Vec<String> = candidates.iter()
+                .map(|item| format!("  {}\t{}\t{}", index(item), item.id, item.title))
+                .collect();
+            bail!("\"{}\" matches {} todos; use a longer id prefix:\n{}", reference, candidates.len(), lines.join("\n"))
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn items() -> Vec<TodoItem> {
+        ["3aaa0000-0000-4000-8000-000000000000", "3abb0000-0000-4000-8000-000000000000", "2000abcd-0000-4000-8000-000000000000"]
+            .iter()
+            .enumerate()
+            .map(|(position, id)| {
+                let mut item = TodoItem::new(format!("Todo {}", position + 1), None);
+                item.id = Uuid::parse_str(id).unwrap();
+                item.order = position as u32;
+                item
+            })
+            .collect()
+    }
+
+    fn title(items: &[TodoItem], reference: &str) -> String {
+        find(items, reference).unwrap().title.clone()
+    }
+
+    #[test]
+    fn markdown_escapes_titles() {
+        let mut items = items();
+        items[0].title = "# not a heading".to_string();
+        items[1].title = "[ ] not a checkbox\nsecond line".to_string();
+        items[2].title = "Ask about #budget".to_string();
+        items[2].tags.push("work".to_string());
+
+        assert_eq!(markdown(&items), concat!(
+            "- [ ] \\# not a heading\n",
+            "- [ ] \\[ ] not a checkbox second line\n",
+            "- [ ] Ask about \\#budget #work\n",
+        ));
+    }
+
+    #[test]
+    fn finds_by_index_or_id_prefix() {
+        let items = items();
+        assert_eq!(title(&items, "2"), "Todo 2");
+        assert_eq!(title(&items, "#3"), "Todo 3");
+        assert_eq!(title(&items, "3AA"), "Todo 1");
+        assert_eq!(title(&items, " 3ab "), "Todo 2");
+        assert_eq!(title(&items, "2000"), "Todo 3");
+    }
+
+    #[test]
+    fn id_form_skips_indexes() {
+        let items = items();
+        assert_eq!(title(&items, "id:2"), "Todo 3");
+        assert_eq!(title(&items, "ID:3ab"), "Todo 2");
+    }
+
+    #[test]
+    fn rejects_unknown_and_ambiguous_references() {
+        let items = items();
+        assert!(find(&items, "").is_err());
+        assert!(find(&items, "#4").is_err());
+        assert!(find(&items, "#2a").is_err());
+        assert!(find(&items, "id:").is_err());
+        assert!(find(&items, "ff").is_err());
+
+        let error = find(&items, "3a").unwrap_err().to_string();
+        assert!(error.contains("matches 2 todos"), "{}", error);
+    }
+}
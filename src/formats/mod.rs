@@ -0,0 +1,90 @@
+pub mod ics;
+pub mod list;
+pub mod taskwarrior;
+pub mod todotxt;
+
+use crate::models::{TodoItem, TodoList};
+use chrono::Local;
+use serde_json::Value;
+use std::collections::HashMap;
+use uuid::Uuid;
+
+#[derive(Debug, Default)]
+pub struct ImportSummary {
+    pub added: usize,
+    pub updated: usize,
+    pub skipped: usize,
+}
+
+pub fn import_items(todo_list: &mut TodoList, items: Vec<TodoItem>) -> ImportSummary {
+    let mut summary = ImportSummary::default();
+    let initial = todo_list.initial_state().to_string();
+    let final_state = todo_list.final_state().to_string();
+    let mut next_order = todo_list.items.iter().map(|item| item.order + 1).max().unwrap_or(0);
+
+    // Todos imported before keep their id, and so do the links to them from their children.
+    let remapped: HashMap<Uuid, Uuid> = items.iter()
+        .filter_map(|item| {
+            let source = external_id(item)?;
+            let existing = todo_list.items.iter().find(|existing| external_id(existing) == Some(source))?;
+            Some((item.id, existing.id))
+        })
+        .collect();
+
+    for mut item in items {
+        if !todo_list.workflow.contains(&item.status) {
+            item.status = if item.is_completed() { final_state.clone() } else { initial.clone() };
+        }
+
+        if let Some(id) = remapped.get(&item.id) {
+            item.id = *id;
+        }
+        if let Some(parent_id) = item.parent_id.and_then(|parent_id| remapped.get(&parent_id)) {
+            item.parent_id = Some(*parent_id);
+        }
+
+        if let Some(existing) = todo_list.get_todo_by_id_mut(&item.id) {
+            item.order = existing.order;
+            item.expanded = existing.expanded;
+            if *existing != item {
+                *existing = item;
+                summary.updated += 1;
+            } else {
+                summary.skipped += 1;
+            }
+            continue;
+        }
+
+        let created = item.created_at.with_timezone(&Local).date_naive();
+        let duplicate = todo_list.items.iter().any(|existing| {
+            existing.title == item.title && existing.created_at.with_timezone(&Local).date_naive() == created
+        });
+        if duplicate {
+            summary.skipped += 1;
+            continue;
+        }
+
+        item.order = next_order;
+        next_order += 1;
+        todo_list.items.push(item);
+        summary.added += 1;
+    }
+
+    let orphans: Vec<_> = todo_list.items.iter()
+        .filter(|item| item.parent_id.is_some_and(|parent| todo_list.get_todo_by_id(&parent).is_none()))
+        .map(|item| item.id)
+        .collect();
+    for item in todo_list.items.iter_mut().filter(|item| orphans.contains(&item.id)) {
+        item.parent_id = None;
+    }
+
+    todo_list.normalize_statuses();
+    todo_list.normalize_order();
+    summary
+}
+
+fn external_id(item: &TodoItem) -> Option<(&str, &str)> {
+    item.extensions.iter().find_map(|(format, extension)| {
+        extension.get("uid").and_then(Value::as_str).map(|uid| (format.as_str(), uid))
+    })
+}
@@ -0,0 +1,297 @@
+use crate::models::{Priority, TodoItem, TodoList};
+use anyhow::{bail, Context, Result};
+use chrono::{DateTime, NaiveDateTime, Utc};
+use serde_json::{json, Map, Value};
+use uuid::Uuid;
+
+pub const EXTENSION_KEY: &str = "taskwarrior";
+const DATE_FORMAT: &str = "%Y%m%dT%H%M%SZ";
+const COMPUTED_FIELDS: [&str; 2] = ["id", "urgency"];
+// Taskwarrior has no priority above `H`, so Urgent todos carry this attribute to come back as Urgent.
+const PRIORITY_FIELD: &str = "termtask_priority";
+
+pub fn parse(content: &str) -> Result<Vec<TodoItem>> {
+    let tasks: Vec<Value> = match serde_json::from_str(content) {
+        Ok(Value::Array(tasks)) => tasks,
+        Ok(task @ Value::Object(_)) => vec![task],
+        _ => content.lines()
+            .map(|line| line.trim().trim_end_matches(','))
+            .filter(|line| !line.is_empty() && *line != "[" && *line != "]")
+            .map(|line| serde_json::from_str(line).with_context(|| format!("Not a Taskwarrior task: {}", line)))
+            .collect::<Result<_>>()?,
+    };
+
+    tasks.into_iter()
+        .map(|task| match task {
+            Value::Object(fields) => Ok(item_from_task(fields)),
+            other => bail!("Not a Taskwarrior task: {}", other),
+        })
+        .collect()
+}
+
+pub fn write(todo_list: &TodoList) -> Result<String> {
+    let mut items: Vec<&TodoItem> = todo_list.items.iter().collect();
+    items.sort_by_key(|item| item.order);
+
+    let tasks = items.into_iter()
+        .map(|item| serde_json::to_string(&task_from_item(item)))
+        .collect::<Result<Vec<_>, _>>()?;
+    Ok(format!("[\n{}\n]\n", tasks.join(",\n")))
+}
+
+/// Deleted tasks are only kept by Taskwarrior for undo and sync, so they are not imported.
+pub fn is_deleted(item: &TodoItem) -> bool {
+    item.extensions.get(EXTENSION_KEY)
+        .and_then(|fields| fields.get("status"))
+        .is_some_and(|status| status == "deleted")
+}
+
+fn take_str(fields: &mut Map<String, Value>, key: &str) -> Option<String> {
+    match fields.remove(key) {
+        Some(Value::String(value)) => Some(value),
+        Some(other) => {
+            fields.insert(key.to_string(), other);
+            None
+        }
+        None => None,
+    }
+}
+
+fn item_from_task(mut fields: Map<String, Value>) -> TodoItem {
+    let mut item = TodoItem::new(take_str(&mut fields, "description").unwrap_or_default(), None);
+    item.status = String::new();
+
+    if let Some(uuid) = take_str(&mut fields, "uuid") {
+        match Uuid::parse_str(&uuid) {
+            Ok(id) => item.id = id,
+            Err(_) => {
+                fields.insert("uuid".to_string(), json!(uuid));
+            }
+        }
+    }
+
+    let status = take_str(&mut fields, "status").unwrap_or_else(|| "pending".to_string());
+    let end = take_str(&mut fields, "end").and_then(|end| parse_date(&end));
+    if let Some(created_at) = take_str(&mut fields, "entry").and_then(|entry| parse_date(&entry)) {
+        item.created_at = created_at;
+    }
+    item.updated_at = take_str(&mut fields, "modified").and_then(|modified| parse_date(&modified));
+    item.due_at = take_str(&mut fields, "due").and_then(|due| parse_date(&due));
+
+    match take_str(&mut fields, "priority").as_deref() {
+        Some("H") => item.priority = Priority::High,
+        Some("M") => item.priority = Priority::Medium,
+        Some("L") => item.priority = Priority::Low,
+        Some(other) => {
+            fields.insert("priority".to_string(), json!(other));
+        }
+        None => {}
+    }
+    let urgent = take_str(&mut fields, PRIORITY_FIELD).is_some_and(|priority| priority == Priority::Urgent.name());
+    if urgent && item.priority == Priority::High {
+        item.priority = Priority::Urgent;
+    }
+
+    if status == "completed" || status == "deleted" {
+        item.completed_at = Some(end.unwrap_or_else(Utc::now));
+    }
+    if status != "pending" && status != "completed" {
+        fields.insert("status".to_string(), json!(status));
+    }
+
+    if let Some(Value::Array(tags)) = fields.remove("tags") {
+        item.tags = tags.iter().filter_map(Value::as_str).map(str::to_string).collect();
+    }
+
+    if let Some(Value::Array(annotations)) = fields.get("annotations") {
+        let description = annotations.iter()
+            .filter_map(|annotation| annotation.get("description").and_then(Value::as_str))
+            .collect::<Vec<_>>()
+            .join("\n");
+        item.description = (!description.is_empty()).then_some(description);
+    }
+
+    for field in COMPUTED_FIELDS {
+        fields.remove(field);
+    }
+    if !fields.is_empty() {
+        item.extensions.insert(EXTENSION_KEY.to_string(), Value::Object(fields));
+    }
+
+    item
+}
+
+fn task_from_item(item: &TodoItem) -> Map<String, Value> {
+    let mut fields = match item.extensions.get(EXTENSION_KEY) {
+        Some(Value::Object(fields)) => fields.clone(),
+        _ => Map::new(),
+    };
+
+    let original_status = take_str(&mut fields, "status");
+    let status = match original_status.as_deref() {
+        Some(status @ "deleted") if item.is_completed() => status,
+        Some(status @ ("waiting" | "recurring")) if !item.is_completed() => status,
+        _ if item.is_completed() => "completed",
+        _ => "pending",
+    };
+
+    fields.entry("uuid").or_insert_with(|| json!(item.id.to_string()));
+    fields.insert("description".to_string(), json!(item.title));
+    fields.insert("status".to_string(), json!(status));
+    fields.insert("entry".to_string(), json!(format_date(&item.created_at)));
+    fields.insert("modified".to_string(), json!(format_date(&item.last_changed())));
+
+    match &item.completed_at {
+        Some(completed_at) => fields.insert("end".to_string(), json!(format_date(completed_at))),
+        None => fields.remove("end"),
+    };
+    if let Some(due_at) = &item.due_at {
+        fields.insert("due".to_string(), json!(format_date(due_at)));
+    }
+
+    let priority = match item.priority {
+        Priority::Urgent | Priority::High => Some("H"),
+        Priority::Medium => Some("M"),
+        Priority::Low => Some("L"),
+        Priority::None => None,
+    };
+    if let Some(priority) = priority {
+        fields.insert("priority".to_string(), json!(priority));
+    }
+    if item.priority == Priority::Urgent {
+        fields.insert(PRIORITY_FIELD.to_string(), json!(Priority::Urgent.name()));
+    }
+
+    if !item.tags.is_empty() {
+        let tags: Vec<String> = item.tags.iter().map(|tag| tag.split_whitespace().collect::<Vec<_>>().join("_")).collect();
+        fields.insert("tags".to_string(), json!(tags));
+    }
+
+    let kept_annotations = fields.get("annotations")
+        .and_then(Value::as_array)
+        .is_some_and(|annotations| {
+            let text: Vec<&str> = annotations.iter()
+                .filter_map(|annotation| annotation.get("description").and_then(Value::as_str))
+                .collect();
+            text.join("\n") == item.description.as_deref().unwrap_or_default()
+        });
+    if !kept_annotations {
+        let entry = format_date(&item.last_changed());
+        let annotations: Vec<Value> = item.description.as_deref().unwrap_or_default()
+            .lines()
+            .filter(|line| !line.trim().is_empty())
+            .map(|line| json!({ "entry": entry, "description": line }))
+            .collect();
+        if annotations.is_empty() {
+            fields.remove("annotations");
+        } else {
+            fields.insert("annotations".to_string(), Value::Array(annotations));
+        }
+    }
+
+    fields
+}
+
+fn parse_date(value: &str) -> Option<DateTime<Utc>> {
+    NaiveDateTime::parse_from_str(value, DATE_FORMAT)
+        .map(|naive| naive.and_utc())
+        .ok()
+        .or_else(|| DateTime::parse_from_rfc3339(value).ok().map(|date| date.with_timezone(&Utc)))
+}
+
+fn format_date(date: &DateTime<Utc>) -> String {
+    date.format(DATE_FORMAT).to_string()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::TimeZone;
+
+    const EXPORT: &str = r#"[
+{"id":1,"description":"Write report","entry":"20240301T090000Z","modified":"20240302T100000Z","due":"20240305T170000Z","priority":"H","project":"work","status":"pending","tags":["office","q1"],"urgency":9.2,"uuid":"0b6f1a4e-59f1-4a3c-9a47-0a8f2a5b6c7d","annotations":[{"entry":"20240301T091500Z","description":"Ask Sam"},{"entry":"20240301T091600Z","description":"Use the template"}]},
+{"id":0,"description":"Old task","end":"20240303T080000Z","entry":"20240301T080000Z","status":"deleted","urgency":0,"uuid":"5c1e2d3f-4a5b-4c6d-8e7f-9a0b1c2d3e4f"}
+]"#;
+
+    fn task(item: &TodoItem) -> Value {
+        Value::Object(task_from_item(item))
+    }
+
+    #[test]
+    fn reads_exports() {
+        let items = parse(EXPORT).unwrap();
+        assert_eq!(items.len(), 2);
+
+        let report = &items[0];
+        assert_eq!(report.title, "Write report");
+        assert_eq!(report.priority, Priority::High);
+        assert_eq!(report.tags, vec!["office".to_string(), "q1".to_string()]);
+        assert_eq!(report.description.as_deref(), Some("Ask Sam\nUse the template"));
+        assert_eq!(report.due_at, Some(Utc.with_ymd_and_hms(2024, 3, 5, 17, 0, 0).unwrap()));
+        assert!(!report.is_completed());
+        assert_eq!(report.extensions[EXTENSION_KEY]["project"], "work");
+        assert!(report.extensions[EXTENSION_KEY].get("urgency").is_none());
+
+        let deleted = &items[1];
+        assert_eq!(deleted.completed_at, Some(Utc.with_ymd_and_hms(2024, 3, 3, 8, 0, 0).unwrap()));
+        assert!(is_deleted(deleted));
+        assert!(!is_deleted(report));
+    }
+
+    #[test]
+    fn urgent_todos_come_back_urgent() {
+        let mut item = TodoItem::new("Fix the outage".to_string(), None);
+        item.priority = Priority::Urgent;
+        let exported = task(&item);
+        assert_eq!(exported["priority"], "H");
+
+        let again = item_from_task(exported.as_object().unwrap().clone());
+        assert_eq!(again.priority, Priority::Urgent);
+        assert!(again.extensions.get(EXTENSION_KEY).and_then(|fields| fields.get(PRIORITY_FIELD)).is_none());
+
+        item.priority = Priority::High;
+        assert_eq!(item_from_task(task(&item).as_object().unwrap().clone()).priority, Priority::High);
+    }
+
+    #[test]
+    fn reads_one_task_per_line() {
+        let lines = "{\"description\":\"One\"},\n{\"description\":\"Two\",\"status\":\"completed\"}\n";
+        let items = parse(lines).unwrap();
+        assert_eq!(items.iter().map(|item| item.title.as_str()).collect::<Vec<_>>(), vec!["One", "Two"]);
+        assert!(items[1].is_completed());
+
+        assert!(parse("[1, 2]").is_err());
+        assert!(parse("not json").is_err());
+    }
+
+    #[test]
+    fn round_trips_tasks() {
+        let mut todo_list = TodoList::new();
+        todo_list.items = parse(EXPORT).unwrap();
+        todo_list.normalize_statuses();
+        todo_list.normalize_order();
+
+        let again = parse(&write(&todo_list).unwrap()).unwrap();
+        for (read, written) in again.iter().zip(&todo_list.items) {
+            assert_eq!(task(read), task(written));
+        }
+
+        let report = task(&todo_list.items[0]);
+        assert_eq!(report["annotations"][0]["entry"], "20240301T091500Z");
+        assert_eq!(report["status"], "pending");
+        assert_eq!(task(&todo_list.items[1])["status"], "deleted");
+    }
+
+    #[test]
+    fn edited_descriptions_replace_annotations() {
+        let mut item = parse(EXPORT).unwrap().remove(0);
+        item.description = Some("Ask Sam\n\nThen send it".to_string());
+
+        let annotations = task(&item)["annotations"].as_array().unwrap().clone();
+        let text: Vec<&str> = annotations.iter().filter_map(|annotation| annotation["description"].as_str()).collect();
+        assert_eq!(text, vec!["Ask Sam", "Then send it"]);
+
+        item.description = None;
+        assert!(task(&item).get("annotations").is_none());
+    }
+}
@@ -0,0 +1,299 @@
+use crate::models::{Priority, TodoItem, TodoList};
+use chrono::{DateTime, Local, NaiveDate, NaiveTime, TimeZone, Utc};
+use serde_json::{json, Value};
+
+pub const EXTENSION_KEY: &str = "todotxt";
+const DATE_FORMAT: &str = "%Y-%m-%d";
+
+pub fn parse(content: &str) -> Vec<TodoItem> {
+    content.lines().filter_map(parse_line).collect()
+}
+
+pub fn parse_line(line: &str) -> Option<TodoItem> {
+    let mut words: Vec<&str> = line.split_whitespace().collect();
+    if words.is_empty() {
+        return None;
+    }
+    words.reverse();
+
+    let completed = words.last() == Some(&"x");
+    if completed {
+        words.pop();
+    }
+
+    let mut priority_letter = words.last().and_then(|word| parse_priority(word));
+    if priority_letter.is_some() {
+        words.pop();
+    }
+
+    let mut completed_at = None;
+    let mut created_at = None;
+    if let Some(date) = words.last().and_then(|word| parse_date(word)) {
+        words.pop();
+        match words.last().and_then(|word| parse_date(word)) {
+            Some(created) if completed => {
+                words.pop();
+                completed_at = Some(date);
+                created_at = Some(created);
+            }
+            _ if completed => completed_at = Some(date),
+            _ => created_at = Some(date),
+        }
+    }
+    words.reverse();
+
+    let mut trailing = Vec::new();
+    while words.len() > 1 && words.last().is_some_and(|word| is_token(word)) {
+        trailing.insert(0, words.pop().unwrap_or_default());
+    }
+
+    let mut item = TodoItem::new(original_text(line, &words).to_string(), None);
+    item.status = String::new();
+    item.completed_at = if completed { Some(completed_at.unwrap_or_else(Utc::now)) } else { None };
+    item.created_at = created_at.or(item.completed_at).unwrap_or(item.created_at);
+
+    for word in words.iter().chain(trailing.iter()) {
+        if let Some(tag) = tag_from_token(word) {
+            if !item.has_tag(&tag) {
+                item.tags.push(tag);
+            }
+        }
+    }
+
+    let mut unknown = Vec::new();
+    let mut priority_token = false;
+    for token in trailing.iter().filter(|token| tag_from_token(token).is_none()) {
+        let (key, value) = token.split_once(':').unwrap_or((token, ""));
+        match key {
+            "due" if item.due_at.is_none() => match parse_date(value) {
+                Some(due_at) => item.due_at = Some(due_at),
+                None => unknown.push(token.to_string()),
+            },
+            "pri" if priority_letter.is_none() => match parse_priority(&format!("({})", value)) {
+                Some(letter) => {
+                    priority_letter = Some(letter);
+                    priority_token = true;
+                }
+                None => unknown.push(token.to_string()),
+            },
+            _ => unknown.push(token.to_string()),
+        }
+    }
+
+    let mut extension = serde_json::Map::new();
+    if created_at.is_none() {
+        extension.insert("undated".to_string(), json!(true));
+    }
+    // The line had no completion date, so `completed_at` is a stand-in; remember which one, so a
+    // todo completed again later still gets its real date written.
+    if let Some(stand_in) = item.completed_at.filter(|_| completed_at.is_none()) {
+        extension.insert("completed_undated".to_string(), json!(stand_in));
+    }
+    if let Some(letter) = priority_letter {
+        item.priority = priority_from_letter(letter);
+        if letter_from_priority(item.priority) != Some(letter) {
+            extension.insert("priority".to_string(), json!(letter.to_string()));
+        }
+        if priority_token {
+            extension.insert("pri_token".to_string(), json!(true));
+        }
+    }
+    if !unknown.is_empty() {
+        extension.insert("tokens".to_string(), json!(unknown));
+    }
+    if !extension.is_empty() {
+        item.extensions.insert(EXTENSION_KEY.to_string(), Value::Object(extension));
+    }
+
+    Some(item)
+}
+
+pub fn write(todo_list: &TodoList) -> String {
+    let mut items: Vec<&TodoItem> = todo_list.items.iter().collect();
+    items.sort_by_key(|item| item.order);
+    items.into_iter().map(|item| format_line(item) + "\n").collect()
+}
+
+pub fn format_line(item: &TodoItem) -> String {
+    let extension = item.extensions.get(EXTENSION_KEY);
+    let priority = extension
+        .and_then(|extension| extension.get("priority"))
+        .and_then(Value::as_str)
+        .and_then(|letter| letter.chars().next())
+        .filter(|letter| priority_from_letter(*letter) == item.priority)
+        .or_else(|| letter_from_priority(item.priority));
+
+    let flag = |key: &str| extension.and_then(|extension| extension.get(key)).is_some_and(|value| value == true);
+    let priority_token = flag("pri_token");
+    let undated_completion = extension
+        .and_then(|extension| extension.get("completed_undated"))
+        .and_then(|stand_in| serde_json::from_value::<DateTime<Utc>>(stand_in.clone()).ok());
+
+    let mut parts = Vec::new();
+    if item.is_completed() {
+        parts.push("x".to_string());
+    }
+    if let Some(letter) = priority.filter(|_| !priority_token) {
+        parts.push(format!("({})", letter));
+    }
+    if let Some(completed_at) = item.completed_at.as_ref().filter(|completed_at| Some(**completed_at) != undated_completion) {
+        parts.push(format_date(completed_at));
+    }
+    if !flag("undated") {
+        parts.push(format_date(&item.created_at));
+    }
+
+    let title = item.title.split_whitespace().collect::<Vec<_>>();
+    if !title.is_empty() {
+        parts.push(item.title.trim().replace(['\r', '\n'], " "));
+    }
+
+    let mut tags: Vec<String> = item.tags.iter()
+        .map(|tag| {
+            let tag = tag.split_whitespace().collect::<Vec<_>>().join("-");
+            if tag.starts_with('@') { tag } else { format!("+{}", tag) }
+        })
+        .filter(|token| !title.iter().any(|word| word.eq_ignore_ascii_case(token)))
+        .collect();
+    tags.sort_by_key(|token| token.starts_with('@'));
+    parts.extend(tags);
+
+    if let Some(due_at) = &item.due_at {
+        parts.push(format!("due:{}", format_date(due_at)));
+    }
+
+    let tokens = extension.and_then(|extension| extension.get("tokens")).and_then(Value::as_array);
+    parts.extend(tokens.into_iter().flatten().filter_map(Value::as_str).map(str::to_string));
+
+    if let Some(letter) = priority.filter(|_| priority_token) {
+        parts.push(format!("pri:{}", letter));
+    }
+
+    parts.join(" ")
+}
+
+/// The part of `line` from the first to the last of `words`, with its spacing intact.
+fn original_text<'a>(line: &'a str, words: &[&str]) -> &'a str {
+    let offset = |word: &str| word.as_ptr() as usize - line.as_ptr() as usize;
+    match (words.first(), words.last()) {
+        (Some(first), Some(last)) => &line[offset(first)..offset(last) + last.len()],
+        _ => "",
+    }
+}
+
+fn is_token(word: &str) -> bool {
+    if tag_from_token(word).is_some() {
+        return true;
+    }
+    match word.split_once(':') {
+        Some((key, value)) => {
+            !key.is_empty() && !value.is_empty() && !value.starts_with("//") && !key.contains(':')
+        }
+        None => false,
+    }
+}
+
+fn tag_from_token(word: &str) -> Option<String> {
+    if let Some(project) = word.strip_prefix('+').filter(|project| !project.is_empty()) {
+        Some(project.to_string())
+    } else if word.len() > 1 && word.starts_with('@') {
+        Some(word.to_string())
+    } else {
+        None
+    }
+}
+
+fn parse_priority(word: &str) -> Option<char> {
+    let letter = word.strip_prefix('(')?.strip_suffix(')')?;
+    let mut chars = letter.chars();
+    let letter = chars.next().filter(|letter| letter.is_ascii_uppercase())?;
+    chars.next().is_none().then_some(letter)
+}
+
+fn priority_from_letter(letter: char) -> Priority {
+    match letter {
+        'A' => Priority::Urgent,
+        'B' => Priority::High,
+        'C' => Priority::Medium,
+        _ => Priority::Low,
+    }
+}
+
+fn letter_from_priority(priority: Priority) -> Option<char> {
+    match priority {
+        Priority::None => None,
+        Priority::Low => Some('D'),
+        Priority::Medium => Some('C'),
+        Priority::High => Some('B'),
+        Priority::Urgent => Some('A'),
+    }
+}
+
+fn parse_date(word: &str) -> Option<DateTime<Utc>> {
+    let date = NaiveDate::parse_from_str(word, DATE_FORMAT).ok()?;
+    let local = Local.from_local_datetime(&date.and_time(NaiveTime::MIN)).earliest()?;
+    Some(local.with_timezone(&Utc))
+}
+
+fn format_date(date: &DateTime<Utc>) -> String {
+    date.with_timezone(&Local).format(DATE_FORMAT).to_string()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn round_trip(line: &str) -> String {
+        format_line(&parse_line(line).unwrap())
+    }
+
+    #[test]
+    fn lines_survive_a_round_trip() {
+        for line in [
+            "(B) 2024-03-01 Write report +office @work",
+            "x (A) 2024-03-03 2024-03-01 Call  mom +family due:2024-03-05 foo:bar",
+            "x 2024-03-03 Done thing",
+            "x Done thing",
+            "x (B) Done without dates",
+            "Undated   task pri:C",
+            "x 2024-03-03 2024-03-01 Old urgent task pri:A",
+            "(E) Rare priority",
+        ] {
+            assert_eq!(round_trip(line), line);
+        }
+    }
+
+    #[test]
+    fn reads_completion_priority_and_dates() {
+        let item = parse_line("x (A) 2024-03-03 2024-03-01 Call  mom +family @phone due:2024-03-05").unwrap();
+        assert!(item.is_completed());
+        assert_eq!(item.priority, Priority::Urgent);
+        assert_eq!(item.title, "Call  mom");
+        assert_eq!(item.tags, vec!["family".to_string(), "@phone".to_string()]);
+        assert_eq!(format_date(&item.created_at), "2024-03-01");
+        assert_eq!(item.completed_at.as_ref().map(format_date).as_deref(), Some("2024-03-03"));
+        assert_eq!(item.due_at.as_ref().map(format_date).as_deref(), Some("2024-03-05"));
+    }
+
+    #[test]
+    fn completing_an_undated_todo_again_writes_the_date() {
+        let mut item = parse_line("x Done thing").unwrap();
+        item.completed_at = parse_date("2024-03-04");
+        assert_eq!(format_line(&item), "x 2024-03-04 Done thing");
+    }
+
+    #[test]
+    fn due_dates_must_be_strict() {
+        let item = parse_line("Buy milk due:tomorrow").unwrap();
+        assert_eq!(item.due_at, None);
+        assert_eq!(format_line(&item), "Buy milk due:tomorrow");
+    }
+
+    #[test]
+    fn writes_whole_lists_in_order() {
+        let mut todo_list = TodoList::new();
+        todo_list.items = parse("First\n\nSecond\n");
+        todo_list.normalize_order();
+        assert_eq!(write(&todo_list), "First\nSecond\n");
+    }
+}
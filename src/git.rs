@@ -1,3 +1,4 @@
+use std::collections::HashMap;
 use std::env;
 use std::path::{Path, PathBuf};
 
@@ -50,13 +51,100 @@ impl GitRepository {
         self.root.join(".gitignore")
     }
 
+    pub fn get_gitattributes_path(&self) -> PathBuf {
+        self.root.join(".gitattributes")
+    }
+
     pub fn add_to_gitignore(&self, entry: &str) -> Result<(), std::io::Error> {
+        Self::append_line(&self.get_gitignore_path(), entry)
+    }
+
+    pub fn add_to_gitattributes(&self, entry: &str) -> Result<(), std::io::Error> {
+        Self::append_line(&self.get_gitattributes_path(), entry)
+    }
+
+    pub fn set_config(&self, key: &str, value: &str) -> Result<(), std::io::Error> {
+        use std::io::Error;
+        use std::process::Command;
+
+        let status = Command::new("git")
+            .args(["config", "--local", key, value])
+            .current_dir(&self.root)
+            .status()?;
+
+        if status.success() {
+            Ok(())
+        } else {
+            Err(Error::other(format!("git config {} failed", key)))
+        }
+    }
+
+    pub fn list_files(&self) -> Result<Vec<PathBuf>, std::io::Error> {
+        let output = self.run_git(&["ls-files", "-z", "--cached", "--others", "--exclude-standard"])?;
+        let mut files: Vec<PathBuf> = output
+            .split('\0')
+            .filter(|path| !path.is_empty())
+            .map(PathBuf::from)
+            .collect();
+        files.dedup();
+        Ok(files)
+    }
+
+    pub fn blame_authors(&self, file: &Path) -> Result<HashMap<usize, String>, std::io::Error> {
+        let file = file.to_string_lossy();
+        let output = self.run_git(&["blame", "--line-porcelain", "--", file.as_ref()])?;
+
+        let mut authors = HashMap::new();
+        let mut line = 0;
+        for entry in output.lines() {
+            let mut fields = entry.split(' ');
+            if let (Some(hash), Some(_), Some(final_line)) = (fields.next(), fields.next(), fields.next()) {
+                if hash.len() == 40 && hash.chars().all(|c| c.is_ascii_hexdigit()) {
+                    line = final_line.parse().unwrap_or(0);
+                    continue;
+                }
+            }
+            if let Some(author) = entry.strip_prefix("author ") {
+                if author != "Not Committed Yet" {
+                    authors.insert(line, author.to_string());
+                }
+            }
+        }
+        Ok(authors)
+    }
+
+    pub fn hooks_dir(&self) -> Result<PathBuf, std::io::Error> {
+        let output = self.run_git(&["rev-parse", "--git-path", "hooks"])?;
+        Ok(self.root.join(output.trim()))
+    }
+
+    pub fn head_commit(&self) -> Result<(String, String), std::io::Error> {
+        let output = self.run_git(&["log", "-1", "--format=%H%n%B"])?;
+        let (hash, message) = output.split_once('\n').unwrap_or((output.as_str(), ""));
+        Ok((hash.trim().to_string(), message.to_string()))
+    }
+
+    fn run_git(&self, args: &[&str]) -> Result<String, std::io::Error> {
+        use std::io::Error;
+        use std::process::Command;
+
+        let output = Command::new("git")
+            .args(args)
+            .current_dir(&self.root)
+            .output()?;
+
+        if output.status.success() {
+            Ok(String::from_utf8_lossy(&output.stdout).into_owned())
+        } else {
+            Err(Error::other(format!("git {} failed: {}", args[0], String::from_utf8_lossy(&output.stderr).trim())))
+        }
+    }
+
+    fn append_line(path: &Path, entry: &str) -> Result<(), std::io::Error> {
         use std::fs::{read_to_string, write};
         use std::io::ErrorKind;
 
-        let gitignore_path = self.get_gitignore_path();
-
-        let mut content = match read_to_string(&gitignore_path) {
+        let mut content = match read_to_string(path) {
             Ok(content) => content,
             Err(e) if e.kind() == ErrorKind::NotFound => String::new(),
             Err(e) => return Err(e),
@@ -68,7 +156,7 @@ impl GitRepository {
             }
             content.push_str(entry);
             content.push('\n');
-            write(&gitignore_path, content)?;
+            write(path, content)?;
         }
 
         Ok(())
@@ -0,0 +1,183 @@
+use crate::git::GitRepository;
+use crate::storage::{open_store, JsonStore};
+use anyhow::{bail, Context, Result};
+use std::fs;
+use std::path::{Path, PathBuf};
+use uuid::Uuid;
+
+const MARKER: &str = "# Installed by termtask";
+const KEYWORD: &str = "closes todo:";
+const MIN_PREFIX_LENGTH: usize = 4;
+const HOOKS: [(&str, &str); 2] = [
+    ("commit-msg", "termtask hook commit-msg \"$1\""),
+    ("post-commit", "termtask hook post-commit"),
+];
+
+pub enum InstallOutcome {
+    Installed,
+    Updated,
+    Skipped { command: &'static str },
+}
+
+pub struct LinkedTodo {
+    pub path: PathBuf,
+    pub id: Uuid,
+    pub title: String,
+}
+
+pub fn install(repo: &GitRepository) -> Result<Vec<(PathBuf, InstallOutcome)>> {
+    let hooks_dir = repo.hooks_dir().context("Failed to find the repository's hooks directory")?;
+    fs::create_dir_all(&hooks_dir)
+        .with_context(|| format!("Failed to create {}", hooks_dir.display()))?;
+
+    let mut results = Vec::new();
+    for (name, command) in HOOKS {
+        let path = hooks_dir.join(name);
+        let outcome = match fs::read_to_string(&path) {
+            Ok(existing) if !existing.contains(MARKER) => {
+                results.push((path, InstallOutcome::Skipped { command }));
+                continue;
+            }
+            Ok(_) => InstallOutcome::Updated,
+            Err(_) => InstallOutcome::Installed,
+        };
+
+        let script = format!(
+            "#!/bin/sh\n{}: completes todos referenced as `Closes todo:<id>`\ncommand -v termtask >/dev/null 2>&1 || exit 0\nexec {}\n",
+            MARKER, command
+        );
+        fs::write(&path, script).with_context(|| format!("Failed to write {}", path.display()))?;
+        make_executable(&path)?;
+        results.push((path, outcome));
+    }
+
+    Ok(results)
+}
+
+#[cfg(unix)]
+fn make_executable(path: &Path) -> Result<()> {
+    use std::os::unix::fs::PermissionsExt;
+
+    fs::set_permissions(path, fs::Permissions::from_mode(0o755))
+        .with_context(|| format!("Failed to make {} executable", path.display()))
+}
+
+#[cfg(not(unix))]
+fn make_executable(_path: &Path) -> Result<()> {
+    Ok(())
+}
+
+pub fn closing_references(message: &str) -> Vec<String> {
+    let mut references = Vec::new();
+    for line in message.lines() {
+        if line.starts_with('#') {
+            if line.contains(">8") {
+                break;
+            }
+            continue;
+        }
+
+        let lower = line.to_ascii_lowercase();
+        for (position, _) in lower.match_indices(KEYWORD) {
+            let reference: String = lower[position + KEYWORD.len()..]
+                .chars()
+                .take_while(|c| c.is_alphanumeric() || *c == '-')
+                .collect();
+            let reference = reference.trim_end_matches('-').to_string();
+            let is_id = reference.chars().all(|c| c.is_ascii_hexdigit() || c == '-');
+            if !reference.is_empty() && is_id && !references.contains(&reference) {
+                references.push(reference);
+            }
+        }
+    }
+    references
+}
+
+pub fn todo_files(repo: &GitRepository, custom_path: Option<String>) -> Result<Vec<PathBuf>> {
+    if let Some(path) = custom_path {
+        return Ok(vec![PathBuf::from(path)]);
+    }
+
+    let files: Vec<PathBuf> = [repo.get_project_todo_path(), repo.get_personal_todo_path()]
+        .into_iter()
+        .filter(|path| path.exists())
+        .collect();
+    if !files.is_empty() {
+        return Ok(files);
+    }
+
+    let global = JsonStore::get_default_path()?;
+    Ok(if global.exists() { vec![global] } else { Vec::new() })
+}
+
+pub fn resolve(files: &[PathBuf], reference: &str) -> Result<LinkedTodo> {
+    if reference.len() < MIN_PREFIX_LENGTH {
+        bail!("\"Closes todo:{}\" is too short; use at least {} characters of the todo's id", reference, MIN_PREFIX_LENGTH);
+    }
+
+    let mut matches = Vec::new();
+    for path in files {
+        let todos = open_store(path.clone())?.load()
+            .with_context(|| format!("Failed to read {}", path.display()))?;
+        matches.extend(todos.items.into_iter()
+            .filter(|item| item.id.to_string().starts_with(reference))
+            .map(|item| LinkedTodo { path: path.clone(), id: item.id, title: item.title }));
+    }
+
+    match matches.len() {
+        1 => Ok(matches.remove(0)),
+        0 => {
+            let files: Vec<String> = files.iter().map(|path| path.display().to_string()).collect();
+            bail!("\"Closes todo:{}\" matches no todo in {}", reference, if files.is_empty() { "any todo file".to_string() } else { files.join(", ") })
+        }
+        count => {
+            let lines: Vec<String> = matches.iter()
+                .map(|todo| format!("  {}\t{}", todo.id, todo.title))
+                .collect();
+            bail!("\"Closes todo:{}\" matches {} todos; use a longer id prefix:\n{}", reference, count, lines.join("\n"))
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn finds_closing_references() {
+        let message = "Fix login\n\nCloses todo:9A3F2C.\ncloses TODO:0b6f1a4e-59f1-\nCloses todo:9a3f2c again\n";
+        assert_eq!(closing_references(message), vec!["9a3f2c".to_string(), "0b6f1a4e-59f1".to_string()]);
+    }
+
+    #[test]
+    fn skips_references_that_are_not_ids() {
+        let message = "Closes todo: 9a3f\nCloses todo:\nCloses todo:tomorrow\nCloses todo:12zz\nCloses todo:---\n";
+        assert!(closing_references(message).is_empty());
+    }
+
+    #[test]
+    fn ignores_git_comments_and_the_diff() {
+        let message = "Ship it\n# Closes todo:aaaa\nCloses todo:bbbb\n# ------------------------ >8 ------------------------\nCloses todo:cccc\n";
+        assert_eq!(closing_references(message), vec!["bbbb".to_string()]);
+    }
+
+    #[test]
+    fn resolve_needs_one_matching_todo() {
+        let directory = std::env::temp_dir().join(format!("termtask-hooks-{}", Uuid::new_v4()));
+        fs::create_dir_all(&directory).unwrap();
+        let path = directory.join("todo.json");
+        let store = open_store(path.clone()).unwrap();
+        let mut todos = crate::models::TodoList::new();
+        let id = todos.add_todo("Fix login".to_string(), None);
+        todos.add_todo("Fix logout".to_string(), None);
+        store.save(&todos).unwrap();
+
+        let files = vec![path];
+        let prefix = id.to_string()[..8].to_string();
+        assert_eq!(resolve(&files, &prefix).unwrap().id, id);
+        assert!(resolve(&files, &prefix[..3]).is_err());
+        assert!(resolve(&files, "00000000-0000-0").is_err());
+
+        fs::remove_dir_all(&directory).unwrap();
+    }
+}
@@ -1,20 +1,28 @@
 mod cli;
+mod formats;
 mod git;
+mod hooks;
 mod models;
 mod prompt;
+mod scan;
 mod storage;
 mod ui;
 
-use anyhow::Result;
+use anyhow::{bail, Context, Result};
+use chrono::{DateTime, Duration, Local, Utc};
 use clap::Parser;
-use cli::{Cli, Commands};
+use cli::{Cli, Commands, ExchangeFormat, HookAction, ListFormat};
 use git::GitRepository;
 use prompt::{ProjectInitializer, TodoStorageChoice};
+use std::io::{self, Read, Write};
+use std::path::PathBuf;
+use models::{merge_lists, parse_due_date, CommitLink, TodoList};
+use storage::atomic::write_atomic;
+use storage::lock::FileLock;
+use storage::{open_store, BackupStore, JsonStore, TodoQuery, TodoStore};
 use ui::App;
 
 fn main() -> Result<()> {
-    use std::io::Write;
-
     let log_file = std::fs::OpenOptions::new()
         .create(true)
         .append(true)
@@ -30,8 +38,48 @@ fn main() -> Result<()> {
     let cli = Cli::parse();
 
     match cli.command {
-        Some(Commands::Init { personal }) => {
-            handle_init_command(personal)?;
+        Some(Commands::Init { personal, merge_driver }) => {
+            handle_init_command(personal, merge_driver)?;
+        }
+        Some(Commands::MergeDriver { base, ours, theirs }) => {
+            handle_merge_driver_command(base, ours, theirs)?;
+        }
+        Some(Commands::Import { input, format }) => {
+            handle_import_command(cli.global, cli.file, input, format)?;
+        }
+        Some(Commands::Export { format, output }) => {
+            handle_export_command(cli.global, cli.file, format, output)?;
+        }
+        Some(Commands::Migrate { target }) => {
+            handle_migrate_command(cli.global, cli.file, target)?;
+        }
+        Some(Commands::Add { title, desc, desc_file, stdin }) => {
+            handle_add_command(cli.global, cli.file, title, desc, desc_file, stdin)?;
+        }
+        Some(Commands::List { completed, active: _, all, search, tag, since, format }) => {
+            let completed = if all { None } else { Some(completed) };
+            handle_list_command(cli.global, cli.file, completed, search, tag, since, format)?;
+        }
+        Some(Commands::Done { todos }) => {
+            handle_status_command(cli.global, cli.file, todos, true)?;
+        }
+        Some(Commands::Reopen { todos }) => {
+            handle_status_command(cli.global, cli.file, todos, false)?;
+        }
+        Some(Commands::Rm { todos }) => {
+            handle_rm_command(cli.global, cli.file, todos)?;
+        }
+        Some(Commands::Edit { todo }) => {
+            handle_edit_command(cli.global, cli.file, todo)?;
+        }
+        Some(Commands::Scan { dry_run }) => {
+            handle_scan_command(cli.file, dry_run)?;
+        }
+        Some(Commands::Hook { action }) => {
+            handle_hook_command(cli.file, action)?;
+        }
+        Some(Commands::Restore { backup }) => {
+            handle_restore_command(cli.global, cli.file, backup)?;
         }
         None => {
             let mut app = App::new_with_options(cli.global, cli.file)?;
@@ -42,11 +90,14 @@ fn main() -> Result<()> {
     Ok(())
 }
 
-fn handle_init_command(personal: bool) -> Result<()> {
+fn handle_init_command(personal: bool, merge_driver: bool) -> Result<()> {
     if let Some(repo) = GitRepository::find_repository() {
         if repo.has_todo_file().is_some() {
             println!("Todo file already exists in this project!");
             println!("   Existing file: {}", repo.has_todo_file().unwrap().display());
+            if merge_driver {
+                register_merge_driver(&repo)?;
+            }
             return Ok(());
         }
 
@@ -73,6 +124,9 @@ fn handle_init_command(personal: bool) -> Result<()> {
         }
 
         println!("File created: {}", todo_path.display());
+        if merge_driver {
+            register_merge_driver(&repo)?;
+        }
         println!("Run 'termtask' to start managing your todos!");
     } else {
         println!("Not in a git repository!");
@@ -82,3 +136,523 @@ fn handle_init_command(personal: bool) -> Result<()> {
 
     Ok(())
 }
+
+fn register_merge_driver(repo: &GitRepository) -> Result<()> {
+    repo.set_config("merge.termtask.name", "TermTask todo.json merge")
+        .context("Failed to register merge driver in git config")?;
+    repo.set_config("merge.termtask.driver", "termtask merge-driver %O %A %B")
+        .context("Failed to register merge driver in git config")?;
+    repo.add_to_gitattributes("todo.json merge=termtask")
+        .context("Failed to update .gitattributes")?;
+
+    println!("✓ Registered the termtask merge driver in .git/config");
+    println!("✓ Added 'todo.json merge=termtask' to .gitattributes");
+    println!("✓ Teammates run 'termtask init --merge-driver' once to enable it locally");
+    Ok(())
+}
+
+fn handle_merge_driver_command(base: PathBuf, ours: PathBuf, theirs: PathBuf) -> Result<()> {
+    let base_list = JsonStore::new(base).load()?;
+    let our_list = JsonStore::new(ours.clone()).load()?;
+    let their_list = JsonStore::new(theirs).load()?;
+
+    let merged = merge_lists(&base_list, &our_list, &their_list);
+    let content = serde_json::to_string_pretty(&merged.list)
+        .context("Failed to serialize merged todo list")?;
+    write_atomic(&ours, content.as_bytes())
+        .with_context(|| format!("Failed to write merged todo file: {}", ours.display()))?;
+
+    if merged.conflicts > 0 {
+        eprintln!(
+            "termtask: resolved {} conflicting edit{} by keeping the latest change",
+            merged.conflicts,
+            if merged.conflicts == 1 { "" } else { "s" }
+        );
+    }
+
+    Ok(())
+}
+
+fn resolve_todo_path(force_global: bool, custom_path: Option<String>) -> Result<PathBuf> {
+    if let Some(path) = custom_path {
+        return Ok(PathBuf::from(path));
+    }
+
+    if !force_global {
+        if let Some(existing_todo_path) = GitRepository::find_repository().and_then(|repo| repo.has_todo_file()) {
+            return Ok(existing_todo_path);
+        }
+    }
+
+    JsonStore::get_default_path()
+}
+
+fn handle_add_command(force_global: bool, custom_path: Option<String>, title: Vec<String>, desc: Option<String>, desc_file: Option<PathBuf>, stdin: bool) -> Result<()> {
+    let title = title.join(" ").trim().to_string();
+    if title.is_empty() {
+        bail!("The todo title cannot be empty");
+    }
+
+    let description = if let Some(path) = desc_file {
+        Some(std::fs::read_to_string(&path)
+            .with_context(|| format!("Failed to read description from {}", path.display()))?)
+    } else if stdin {
+        let mut description = String::new();
+        io::stdin().read_to_string(&mut description)
+            .context("Failed to read description from standard input")?;
+        Some(description)
+    } else {
+        desc
+    };
+    let description = description
+        .map(|description| description.trim_end().to_string())
+        .filter(|description| !description.trim().is_empty());
+
+    let store = open_store(resolve_todo_path(force_global, custom_path)?)?;
+    let mut todos = store.load()?;
+    let id = todos.add_todo(title, description);
+    store.save(&todos)?;
+
+    println!("{}", id);
+    Ok(())
+}
+
+fn handle_list_command(
+    force_global: bool,
+    custom_path: Option<String>,
+    completed: Option<bool>,
+    search: Option<String>,
+    tag: Option<String>,
+    since: Option<String>,
+    format: ListFormat,
+) -> Result<()> {
+    let changed_since = since.as_deref().map(parse_since).transpose()?;
+    let store = open_store(resolve_todo_path(force_global, custom_path)?)?;
+    let items = store.query(&TodoQuery { completed, search, tag, changed_since })?;
+
+    let output = match format {
+        ListFormat::Table => formats::list::table(&items),
+        ListFormat::Plain => formats::list::plain(&items),
+        ListFormat::Json => formats::list::json(&items, store.get_file_path())?,
+        ListFormat::Ndjson => formats::list::ndjson(&items)?,
+        ListFormat::Markdown => formats::list::markdown(&items),
+    };
+    io::stdout().write_all(output.as_bytes())?;
+
+    Ok(())
+}
+
+fn update_todos<T>(force_global: bool, custom_path: Option<String>, update: impl FnOnce(&mut TodoList) -> Result<T>) -> Result<T> {
+    let store = open_store(resolve_todo_path(force_global, custom_path)?)?;
+    let mut todos = store.load()?;
+    let previous = todos.clone();
+
+    let result = update(&mut todos)?;
+    if todos != previous {
+        todos.stamp_changes(&previous);
+        store.save(&todos)?;
+    }
+    Ok(result)
+}
+
+fn find_todos(todos: &TodoList, references: &[String]) -> Result<Vec<(uuid::Uuid, String)>> {
+    references.iter()
+        .map(|reference| formats::list::find(&todos.items, reference).map(|item| (item.id, item.title.clone())))
+        .collect()
+}
+
+fn handle_status_command(force_global: bool, custom_path: Option<String>, references: Vec<String>, complete: bool) -> Result<()> {
+    update_todos(force_global, custom_path, |todos| {
+        for (id, title) in find_todos(todos, &references)? {
+            let is_completed = todos.get_todo_by_id(&id).is_some_and(|item| item.is_completed());
+            if is_completed == complete {
+                println!("Already {}: {}", if complete { "completed" } else { "open" }, title);
+                continue;
+            }
+
+            let status = if complete { todos.final_state() } else { todos.initial_state() }.to_string();
+            todos.set_status(&id, &status);
+            println!("{}: {}", if complete { "Completed" } else { "Reopened" }, title);
+        }
+        Ok(())
+    })
+}
+
+fn handle_rm_command(force_global: bool, custom_path: Option<String>, references: Vec<String>) -> Result<()> {
+    update_todos(force_global, custom_path, |todos| {
+        for (id, title) in find_todos(todos, &references)? {
+            if todos.remove_todo(&id) {
+                println!("Deleted: {}", title);
+            }
+        }
+        Ok(())
+    })
+}
+
+fn handle_edit_command(force_global: bool, custom_path: Option<String>, reference: String) -> Result<()> {
+    update_todos(force_global, custom_path, |todos| {
+        let item = formats::list::find(&todos.items, &reference)?;
+        let id = item.id;
+
+        let mut content = format!("# {}\n", item.title);
+        if let Some(description) = item.description.as_deref().filter(|description| !description.trim().is_empty()) {
+            content.push_str(&format!("\n{}\n", description.trim_end()));
+        }
+
+        let path = std::env::temp_dir().join(format!("termtask-{}-{}.md", formats::list::short_id(item), std::process::id()));
+        std::fs::write(&path, &content)
+            .with_context(|| format!("Failed to create {}", path.display()))?;
+        let edited = run_editor(&path).and_then(|_| {
+            std::fs::read_to_string(&path).with_context(|| format!("Failed to read {}", path.display()))
+        });
+        let _ = std::fs::remove_file(&path);
+        let edited = edited?;
+
+        if edited == content {
+            println!("No changes");
+            return Ok(());
+        }
+
+        let (title, description) = parse_edited_todo(&edited);
+        if title.is_empty() {
+            bail!("The title line (starting with \"# \") is empty; nothing was changed");
+        }
+
+        if let Some(item) = todos.get_todo_by_id_mut(&id) {
+            item.title = title;
+            item.description = description;
+            println!("Updated: {}", item.title);
+        }
+        Ok(())
+    })
+}
+
+fn run_editor(path: &std::path::Path) -> Result<()> {
+    let editor = std::env::var("VISUAL")
+        .or_else(|_| std::env::var("EDITOR"))
+        .ok()
+        .filter(|editor| !editor.trim().is_empty())
+        .unwrap_or_else(|| "vi".to_string());
+
+    let status = std::process::Command::new("sh")
+        .arg("-c")
+        .arg(format!("{} \"$1\"", editor))
+        .arg("termtask")
+        .arg(path)
+        .status()
+        .with_context(|| format!("Failed to start editor: {}", editor))?;
+
+    if !status.success() {
+        bail!("Editor exited with {}; nothing was changed", status);
+    }
+    Ok(())
+}
+
+fn parse_edited_todo(content: &str) -> (String, Option<String>) {
+    let mut lines = content.lines().skip_while(|line| line.trim().is_empty());
+    let title = lines.next().unwrap_or_default();
+    let title = title.strip_prefix('#').unwrap_or(title).trim().to_string();
+
+    let description = lines.collect::<Vec<_>>().join("\n");
+    let description = description.trim_matches('\n').trim_end().to_string();
+    (title, (!description.trim().is_empty()).then_some(description))
+}
+
+fn handle_scan_command(custom_path: Option<String>, dry_run: bool) -> Result<()> {
+    let Some(repo) = GitRepository::find_repository() else {
+        bail!("Not in a git repository; `termtask scan` reads the code of the current repository");
+    };
+
+    let todo_path = match custom_path {
+        Some(path) => PathBuf::from(path),
+        None => repo.has_todo_file().unwrap_or_else(|| repo.get_project_todo_path()),
+    };
+    let skip = [todo_path.clone(), repo.get_project_todo_path(), repo.get_personal_todo_path()];
+    let comments = scan::scan_repository(&repo, &skip)?;
+
+    if dry_run {
+        for comment in &comments {
+            let author = comment.author.as_deref().map(|author| format!(" ({})", author)).unwrap_or_default();
+            println!("{}  {}: {}{}", comment.location(), comment.kind, comment.text, author);
+        }
+        println!("Found {} comment{}", comments.len(), if comments.len() == 1 { "" } else { "s" });
+        return Ok(());
+    }
+
+    let store = open_store(todo_path)?;
+    let mut todos = store.load()?;
+    let previous = todos.clone();
+    let summary = scan::sync(&mut todos, &comments);
+    if todos != previous {
+        todos.stamp_changes(&previous);
+        store.save(&todos)?;
+    }
+
+    println!("Found {} comment{} in {}", comments.len(), if comments.len() == 1 { "" } else { "s" }, repo.root.display());
+    println!("   Added: {}, updated: {}, completed: {}, reopened: {}", summary.added, summary.updated, summary.completed, summary.reopened);
+    println!("   Todo file: {}", store.get_file_path().display());
+
+    Ok(())
+}
+
+fn handle_hook_command(custom_path: Option<String>, action: HookAction) -> Result<()> {
+    let Some(repo) = GitRepository::find_repository() else {
+        bail!("Not in a git repository; `termtask hook` works with the current repository's commits");
+    };
+
+    match action {
+        HookAction::Install => {
+            for (path, outcome) in hooks::install(&repo)? {
+                match outcome {
+                    hooks::InstallOutcome::Installed => println!("Installed {}", path.display()),
+                    hooks::InstallOutcome::Updated => println!("Updated {}", path.display()),
+                    hooks::InstallOutcome::Skipped { command } => {
+                        println!("Skipped {}: the repository already has this hook", path.display());
+                        println!("   Add this line to it: {}", command);
+                    }
+                }
+            }
+            println!("Commit messages containing `Closes todo:<id>` now complete that todo");
+        }
+        HookAction::CommitMsg { message_file } => {
+            let message = std::fs::read_to_string(&message_file)
+                .with_context(|| format!("Failed to read {}", message_file.display()))?;
+            let files = hooks::todo_files(&repo, custom_path)?;
+            for reference in hooks::closing_references(&message) {
+                if let Err(e) = hooks::resolve(&files, &reference) {
+                    eprintln!("termtask: {:#} (the commit goes ahead, but no todo will be completed for it)", e);
+                }
+            }
+        }
+        HookAction::PostCommit => {
+            let (hash, message) = repo.head_commit().context("Failed to read the new commit")?;
+            let summary = message.lines().next().unwrap_or_default().to_string();
+            let files = hooks::todo_files(&repo, custom_path)?;
+
+            for reference in hooks::closing_references(&message) {
+                let linked = match hooks::resolve(&files, &reference) {
+                    Ok(linked) => linked,
+                    Err(e) => {
+                        eprintln!("termtask: {:#}", e);
+                        continue;
+                    }
+                };
+
+                let store = open_store(linked.path)?;
+                let mut todos = store.load()?;
+                let previous = todos.clone();
+                if todos.get_todo_by_id(&linked.id).is_some_and(|item| !item.is_completed()) {
+                    let status = todos.final_state().to_string();
+                    todos.set_status(&linked.id, &status);
+                }
+                if let Some(item) = todos.get_todo_by_id_mut(&linked.id) {
+                    item.closed_by = Some(CommitLink { hash: hash.clone(), summary: summary.clone() });
+                }
+                if todos != previous {
+                    todos.stamp_changes(&previous);
+                    store.save(&todos)?;
+                    println!("termtask: completed \"{}\" (todo:{})", linked.title, &linked.id.to_string()[..8]);
+                }
+            }
+        }
+    }
+
+    Ok(())
+}
+
+fn parse_since(input: &str) -> Result<DateTime<Utc>> {
+    let input = input.trim();
+    let split = input.find(|c: char| !c.is_ascii_digit()).unwrap_or(input.len());
+    let ago = match input.split_at(split) {
+        (amount, "h") => amount.parse().ok().and_then(Duration::try_hours),
+        (amount, "d") => amount.parse().ok().and_then(Duration::try_days),
+        (amount, "w") => amount.parse().ok().and_then(Duration::try_weeks),
+        _ => None,
+    };
+    if let Some(since) = ago.and_then(|ago| Utc::now().checked_sub_signed(ago)) {
+        return Ok(since);
+    }
+
+    parse_due_date(input)
+        .ok()
+        .flatten()
+        .with_context(|| format!("Invalid --since value \"{}\" (use a date such as 2024-03-01, yesterday, 7d or 12h)", input))
+}
+
+fn handle_import_command(force_global: bool, custom_path: Option<String>, input: PathBuf, format: Option<ExchangeFormat>) -> Result<()> {
+    let format = match format {
+        Some(format) => format,
+        None => ExchangeFormat::from_path(&input)
+            .with_context(|| format!("Cannot tell the format of {}; pass --format", input.display()))?,
+    };
+
+    let content = std::fs::read_to_string(&input)
+        .with_context(|| format!("Failed to read {}", input.display()))?;
+    let mut deleted = 0;
+    let items = match format {
+        ExchangeFormat::Todotxt => formats::todotxt::parse(&content),
+        ExchangeFormat::Ics => formats::ics::parse(&content),
+        ExchangeFormat::Taskwarrior => {
+            let (removed, tasks): (Vec<_>, _) = formats::taskwarrior::parse(&content)
+                .with_context(|| format!("Failed to read Taskwarrior tasks from {}", input.display()))?
+                .into_iter()
+                .partition(formats::taskwarrior::is_deleted);
+            deleted = removed.len();
+            tasks
+        }
+    };
+
+    let store = open_store(resolve_todo_path(force_global, custom_path)?)?;
+    let mut todos = store.load()?;
+    let summary = formats::import_items(&mut todos, items);
+    store.save(&todos)?;
+
+    println!(
+        "Imported {} new todo{} into {}",
+        summary.added,
+        if summary.added == 1 { "" } else { "s" },
+        store.get_file_path().display()
+    );
+    if summary.updated > 0 {
+        println!("   Updated: {}", summary.updated);
+    }
+    if summary.skipped > 0 {
+        println!("   Skipped (already present): {}", summary.skipped);
+    }
+    if deleted > 0 {
+        println!("   Skipped (deleted in Taskwarrior): {}", deleted);
+    }
+
+    Ok(())
+}
+
+fn handle_export_command(force_global: bool, custom_path: Option<String>, format: ExchangeFormat, output: Option<PathBuf>) -> Result<()> {
+    let store = open_store(resolve_todo_path(force_global, custom_path)?)?;
+    let todos = store.load()?;
+
+    let content = match format {
+        ExchangeFormat::Todotxt => formats::todotxt::write(&todos),
+        ExchangeFormat::Ics => formats::ics::write(&todos),
+        ExchangeFormat::Taskwarrior => formats::taskwarrior::write(&todos)?,
+    };
+
+    match output {
+        Some(output) => {
+            write_atomic(&output, content.as_bytes())
+                .with_context(|| format!("Failed to write {}", output.display()))?;
+            println!("Exported {} todos to {}", todos.items.len(), output.display());
+        }
+        None => {
+            io::stdout().write_all(content.as_bytes())?;
+        }
+    }
+
+    Ok(())
+}
+
+fn handle_migrate_command(force_global: bool, custom_path: Option<String>, target: PathBuf) -> Result<()> {
+    let source = open_store(resolve_todo_path(force_global, custom_path)?)?;
+    let todos = source.load()?;
+
+    let target = open_store(target)?;
+    if target.get_file_path() == source.get_file_path() {
+        bail!("The target is the current todo file: {}", target.get_file_path().display());
+    }
+    if target.get_file_path().exists() && !target.load()?.items.is_empty() {
+        bail!("Refusing to overwrite existing todos in {}", target.get_file_path().display());
+    }
+
+    target.save(&todos)?;
+
+    let completed = target.query(&TodoQuery { completed: Some(true), ..TodoQuery::default() })?.len();
+    let total = target.query(&TodoQuery::default())?.len();
+    if total != todos.items.len() || completed != todos.get_completed_todos().len() {
+        bail!(
+            "Migration check failed: {} holds {} todos ({} completed), expected {} ({} completed)",
+            target.get_file_path().display(),
+            total,
+            completed,
+            todos.items.len(),
+            todos.get_completed_todos().len()
+        );
+    }
+
+    println!("Migrated {} todos ({} completed)", total, completed);
+    println!("   From: {}", source.get_file_path().display());
+    println!("   To:   {}", target.get_file_path().display());
+    println!("Run 'termtask --file {}' to use it; the original file was left untouched.", target.get_file_path().display());
+
+    Ok(())
+}
+
+fn handle_restore_command(force_global: bool, custom_path: Option<String>, selection: Option<usize>) -> Result<()> {
+    let todo_path = resolve_todo_path(force_global, custom_path)?;
+    let backup_store = BackupStore::for_file(&todo_path)?;
+    let backups = backup_store.list()?;
+
+    if backups.is_empty() {
+        println!("No backups found for {}", todo_path.display());
+        return Ok(());
+    }
+
+    let selection = match selection {
+        Some(selection) => selection,
+        None => {
+            println!("Backups of {}:", todo_path.display());
+            for (index, backup) in backups.iter().enumerate() {
+                let summary = match backup.load() {
+                    Ok(todos) => format!("{} todos, {} completed", todos.items.len(), todos.get_completed_todos().len()),
+                    Err(_) => "unreadable".to_string(),
+                };
+                let original = backup.original_version
+                    .map(|version| format!(", kept before upgrading from v{}", version))
+                    .unwrap_or_default();
+                println!(
+                    "  {:>2}. {}  ({}{})",
+                    index + 1,
+                    backup.created_at.with_timezone(&Local).format("%Y-%m-%d %H:%M:%S"),
+                    summary,
+                    original
+                );
+            }
+
+            print!("Restore which backup? [1-{}, Enter to cancel]: ", backups.len());
+            io::stdout().flush()?;
+
+            let mut input = String::new();
+            io::stdin().read_line(&mut input)?;
+            let input = input.trim();
+            if input.is_empty() {
+                println!("Nothing restored.");
+                return Ok(());
+            }
+
+            match input.parse() {
+                Ok(selection) => selection,
+                Err(_) => bail!("Not a backup number: {}", input),
+            }
+        }
+    };
+
+    let Some(backup) = selection.checked_sub(1).and_then(|index| backups.get(index)) else {
+        bail!("No backup numbered {} (choose 1-{})", selection, backups.len());
+    };
+
+    let todos = backup.load()?;
+    let content = std::fs::read(&backup.path)
+        .with_context(|| format!("Failed to read backup: {}", backup.path.display()))?;
+
+    let _lock = FileLock::acquire(&todo_path)?;
+    backup_store.create(&todo_path)?;
+    write_atomic(&todo_path, &content)
+        .with_context(|| format!("Failed to write todo file: {}", todo_path.display()))?;
+
+    println!(
+        "Restored {} todos from the backup taken {}",
+        todos.items.len(),
+        backup.created_at.with_timezone(&Local).format("%Y-%m-%d %H:%M:%S")
+    );
+    println!("The previous contents were backed up and can be restored the same way.");
+
+    Ok(())
+}
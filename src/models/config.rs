@@ -132,6 +132,12 @@ pub struct UISettings {
     pub vim_mode: bool,
     #[serde(default)]
     pub date_format: DateFormat,
+    #[serde(default = "default_sort_by_priority")]
+    pub sort_by_priority: bool,
+    #[serde(default)]
+    pub board_view: bool,
+    #[serde(default = "default_mouse_capture")]
+    pub mouse_capture: bool,
 }
 
 fn default_vim_mode() -> bool {
@@ -142,6 +148,14 @@ fn default_split_ratio() -> u16 {
     50
 }
 
+fn default_sort_by_priority() -> bool {
+    true
+}
+
+fn default_mouse_capture() -> bool {
+    true
+}
+
 impl Default for UISettings {
     fn default() -> Self {
         Self {
@@ -152,6 +166,9 @@ impl Default for UISettings {
             split_ratio: 50,
             vim_mode: false,
             date_format: DateFormat::default(),
+            sort_by_priority: true,
+            board_view: false,
+            mouse_capture: true,
         }
     }
 }
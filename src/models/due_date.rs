@@ -0,0 +1,217 @@
+use anyhow::{anyhow, Result};
+use chrono::{DateTime, Datelike, Days, Duration, Local, Months, NaiveDate, NaiveDateTime, NaiveTime, TimeZone, Utc, Weekday};
+use crate::models::config::DateFormat;
+
+pub fn parse_due_date(input: &str) -> Result<Option<DateTime<Utc>>> {
+    let input = input.trim().to_lowercase();
+    if input.is_empty() {
+        return Ok(None);
+    }
+
+    let today = Local::now().date_naive();
+    parse_due_date_from(&input, today).map(Some)
+}
+
+fn parse_due_date_from(input: &str, today: NaiveDate) -> Result<DateTime<Utc>> {
+    if let Ok(dt) = DateTime::parse_from_rfc3339(input) {
+        return Ok(dt.with_timezone(&Utc));
+    }
+
+    for format in ["%Y-%m-%d %H:%M", "%Y-%m-%dt%H:%M"] {
+        if let Ok(naive) = NaiveDateTime::parse_from_str(input, format) {
+            return local_to_utc(naive);
+        }
+    }
+
+    if let Ok(date) = NaiveDate::parse_from_str(input, "%Y-%m-%d") {
+        return start_of_day(date);
+    }
+
+    let date = match input {
+        "today" | "tod" => today,
+        "tomorrow" | "tmr" | "tom" => today + Duration::days(1),
+        "yesterday" => today - Duration::days(1),
+        "next week" => today + Duration::weeks(1),
+        "next month" => add_months(today, Some(1))?,
+        _ => {
+            if let Some(weekday) = input.strip_prefix("next ").and_then(parse_weekday) {
+                next_weekday(today, weekday) + Duration::weeks(1)
+            } else if let Some(weekday) = parse_weekday(input) {
+                next_weekday(today, weekday)
+            } else {
+                let offset = input
+                    .strip_prefix("in ")
+                    .or_else(|| input.strip_prefix('+'))
+                    .unwrap_or(input);
+                parse_offset(offset, today)?
+                    .ok_or_else(|| anyhow!("Unrecognized due date: \"{}\"", input))?
+            }
+        }
+    };
+
+    start_of_day(date)
+}
+
+fn parse_weekday(input: &str) -> Option<Weekday> {
+    match input {
+        "mon" | "monday" => Some(Weekday::Mon),
+        "tue" | "tues" | "tuesday" => Some(Weekday::Tue),
+        "wed" | "wednesday" => Some(Weekday::Wed),
+        "thu" | "thur" | "thurs" | "thursday" => Some(Weekday::Thu),
+        "fri" | "friday" => Some(Weekday::Fri),
+        "sat" | "saturday" => Some(Weekday::Sat),
+        "sun" | "sunday" => Some(Weekday::Sun),
+        _ => None,
+    }
+}
+
+fn next_weekday(today: NaiveDate, weekday: Weekday) -> NaiveDate {
+    let current = today.weekday().num_days_from_monday() as i64;
+    let target = weekday.num_days_from_monday() as i64;
+    let mut days = (target - current).rem_euclid(7);
+    if days == 0 {
+        days = 7;
+    }
+    today + Duration::days(days)
+}
+
+fn parse_offset(input: &str, today: NaiveDate) -> Result<Option<NaiveDate>> {
+    let input = input.replace(' ', "");
+    let Some(split) = input.find(|c: char| !c.is_ascii_digit()) else { return Ok(None) };
+    let (amount, unit) = input.split_at(split);
+    let Ok(amount) = amount.parse::<u32>() else { return Ok(None) };
+
+    let date = match unit {
+        "d" | "day" | "days" => add_days(today, Some(amount as u64))?,
+        "w" | "wk" | "week" | "weeks" => add_days(today, (amount as u64).checked_mul(7))?,
+        "m" | "mo" | "month" | "months" => add_months(today, Some(amount))?,
+        "y" | "year" | "years" => add_months(today, amount.checked_mul(12))?,
+        _ => return Ok(None),
+    };
+    Ok(Some(date))
+}
+
+fn add_days(date: NaiveDate, days: Option<u64>) -> Result<NaiveDate> {
+    days.and_then(|days| date.checked_add_days(Days::new(days)))
+        .ok_or_else(|| anyhow!("Due date is out of range"))
+}
+
+fn add_months(date: NaiveDate, months: Option<u32>) -> Result<NaiveDate> {
+    months.and_then(|months| date.checked_add_months(Months::new(months)))
+        .ok_or_else(|| anyhow!("Due date is out of range"))
+}
+
+fn start_of_day(date: NaiveDate) -> Result<DateTime<Utc>> {
+    local_to_utc(date.and_time(NaiveTime::MIN))
+}
+
+fn local_to_utc(naive: NaiveDateTime) -> Result<DateTime<Utc>> {
+    Local
+        .from_local_datetime(&naive)
+        .earliest()
+        .map(|dt| dt.with_timezone(&Utc))
+        .ok_or_else(|| anyhow!("Due date does not exist in the local timezone"))
+}
+
+pub fn due_date_input(dt: &DateTime<Utc>) -> String {
+    let local_dt = dt.with_timezone(&Local);
+    if local_dt.time() == NaiveTime::MIN {
+        local_dt.format("%Y-%m-%d").to_string()
+    } else {
+        local_dt.format("%Y-%m-%d %H:%M").to_string()
+    }
+}
+
+pub fn days_until_due(dt: &DateTime<Utc>) -> i64 {
+    let due_date = dt.with_timezone(&Local).date_naive();
+    (due_date - Local::now().date_naive()).num_days()
+}
+
+pub fn format_due_date(dt: &DateTime<Utc>, format: &DateFormat) -> String {
+    let local_dt = dt.with_timezone(&Local);
+    if local_dt.time() != NaiveTime::MIN {
+        return crate::models::format_datetime(dt, format);
+    }
+
+    match format {
+        DateFormat::AmPm12Hour | DateFormat::Hour24 => local_dt.format("%b %d").to_string(),
+        DateFormat::AmPm12HourWithYear | DateFormat::Hour24WithYear => local_dt.format("%b %d %Y").to_string(),
+        DateFormat::Iso8601 => local_dt.format("%Y-%m-%d").to_string(),
+        DateFormat::Relative => format_relative_due(dt),
+    }
+}
+
+pub fn format_relative_due(dt: &DateTime<Utc>) -> String {
+    match days_until_due(dt) {
+        0 => "today".to_string(),
+        1 => "tomorrow".to_string(),
+        days if days > 1 => format!("in {} days", days),
+        days => format!("overdue {}d", -days),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn today() -> NaiveDate {
+        NaiveDate::from_ymd_opt(2024, 3, 13).unwrap()
+    }
+
+    fn parse(input: &str) -> Result<NaiveDate> {
+        parse_due_date_from(input, today()).map(|dt| dt.with_timezone(&Local).date_naive())
+    }
+
+    fn date(year: i32, month: u32, day: u32) -> NaiveDate {
+        NaiveDate::from_ymd_opt(year, month, day).unwrap()
+    }
+
+    #[test]
+    fn parses_absolute_dates() {
+        assert_eq!(parse("2024-05-01").unwrap(), date(2024, 5, 1));
+        assert_eq!(parse("2024-05-01 14:30").unwrap(), date(2024, 5, 1));
+    }
+
+    #[test]
+    fn parses_relative_words() {
+        assert_eq!(parse("today").unwrap(), today());
+        assert_eq!(parse("tomorrow").unwrap(), date(2024, 3, 14));
+        assert_eq!(parse("next week").unwrap(), date(2024, 3, 20));
+        assert_eq!(parse("next month").unwrap(), date(2024, 4, 13));
+    }
+
+    #[test]
+    fn parses_weekdays() {
+        assert_eq!(parse("fri").unwrap(), date(2024, 3, 15));
+        assert_eq!(parse("wednesday").unwrap(), date(2024, 3, 20));
+        assert_eq!(parse("next fri").unwrap(), date(2024, 3, 22));
+    }
+
+    #[test]
+    fn parses_offsets() {
+        assert_eq!(parse("in 3 days").unwrap(), date(2024, 3, 16));
+        assert_eq!(parse("+2w").unwrap(), date(2024, 3, 27));
+        assert_eq!(parse("in 1 month").unwrap(), date(2024, 4, 13));
+        assert_eq!(parse("1y").unwrap(), date(2025, 3, 13));
+    }
+
+    #[test]
+    fn rejects_unknown_input() {
+        assert!(parse("someday").is_err());
+        let error = parse("in 3 fortnights").unwrap_err();
+        assert_eq!(error.to_string(), "Unrecognized due date: \"in 3 fortnights\"");
+    }
+
+    #[test]
+    fn rejects_offsets_out_of_range() {
+        for input in ["in 999999999d", "in 999999999w", "in 999999999 months", "in 4000000000y"] {
+            let error = parse(input).unwrap_err();
+            assert_eq!(error.to_string(), "Due date is out of range", "{}", input);
+        }
+    }
+
+    #[test]
+    fn empty_input_clears_the_date() {
+        assert!(parse_due_date("  ").unwrap().is_none());
+    }
+}
@@ -0,0 +1,195 @@
+use crate::models::TodoList;
+
+const HISTORY_LIMIT: usize = 100;
+
+pub struct UndoHistory {
+    undo_stack: Vec<TodoList>,
+    redo_stack: Vec<TodoList>,
+}
+
+impl UndoHistory {
+    pub fn new() -> Self {
+        Self {
+            undo_stack: Vec::new(),
+            redo_stack: Vec::new(),
+        }
+    }
+
+    pub fn record(&mut self, before: TodoList) {
+        self.undo_stack.push(before);
+        if self.undo_stack.len() > HISTORY_LIMIT {
+            self.undo_stack.remove(0);
+        }
+        self.redo_stack.clear();
+    }
+
+    pub fn clear(&mut self) {
+        self.undo_stack.clear();
+        self.redo_stack.clear();
+    }
+
+    pub fn undo(&mut self, current: TodoList) -> Option<TodoList> {
+        let previous = self.undo_stack.pop()?;
+        self.redo_stack.push(current);
+        Some(previous)
+    }
+
+    pub fn redo(&mut self, current: TodoList) -> Option<TodoList> {
+        let next = self.redo_stack.pop()?;
+        self.undo_stack.push(current);
+        Some(next)
+    }
+}
+
+pub fn describe_change(from: &TodoList, to: &TodoList) -> String {
+    let added: Vec<&str> = to.items.iter()
+        .filter(|item| from.get_todo_by_id(&item.id).is_none())
+        .map(|item| item.title.as_str())
+        .collect();
+    let removed: Vec<&str> = from.items.iter()
+        .filter(|item| to.get_todo_by_id(&item.id).is_none())
+        .map(|item| item.title.as_str())
+        .collect();
+
+    match (added.as_slice(), removed.as_slice()) {
+        ([title], []) => format!("restored \"{}\"", title),
+        ([], [title]) => format!("removed \"{}\"", title),
+        ([], []) => {
+            let changed = to.items.iter()
+                .find(|item| from.get_todo_by_id(&item.id).is_some_and(|previous| previous != *item))
+                .or_else(|| to.items.iter().zip(&from.items).find(|(a, b)| a.id != b.id).map(|(item, _)| item));
+            match changed {
+                Some(item) => format!("changed \"{}\"", item.title),
+                None => "no visible change".to_string(),
+            }
+        }
+        _ => format!("{} added, {} removed", added.len(), removed.len()),
+    }
+}
+
+pub fn summarize_changes(from: &TodoList, to: &TodoList) -> String {
+    let added = to.items.iter().filter(|item| from.get_todo_by_id(&item.id).is_none()).count();
+    let removed = from.items.iter().filter(|item| to.get_todo_by_id(&item.id).is_none()).count();
+    let (mut completed, mut reopened, mut edited) = (0, 0, 0);
+
+    for item in &to.items {
+        let Some(previous) = from.get_todo_by_id(&item.id) else { continue };
+        match (previous.is_completed(), item.is_completed()) {
+            (false, true) => completed += 1,
+            (true, false) => reopened += 1,
+            _ if previous != item => edited += 1,
+            _ => {}
+        }
+    }
+
+    let parts: Vec<String> = [(added, "added"), (removed, "removed"), (completed, "completed"), (reopened, "reopened"), (edited, "edited")]
+        .iter()
+        .filter(|(count, _)| *count > 0)
+        .enumerate()
+        .map(|(index, (count, verb))| match (index, count) {
+            (0, 1) => format!("1 todo {}", verb),
+            (0, _) => format!("{} todos {}", count, verb),
+            _ => format!("{} {}", count, verb),
+        })
+        .collect();
+
+    if parts.is_empty() {
+        "Todo file reloaded".to_string()
+    } else {
+        parts.join(", ")
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn list_with(titles: &[&str]) -> TodoList {
+        let mut todos = TodoList::new();
+        for title in titles {
+            todos.add_todo(title.to_string(), None);
+        }
+        todos
+    }
+
+    #[test]
+    fn undo_and_redo_walk_the_history() {
+        let mut history = UndoHistory::new();
+        let first = list_with(&["a"]);
+        let second = list_with(&["a", "b"]);
+        let third = list_with(&["a", "b", "c"]);
+
+        history.record(first.clone());
+        history.record(second.clone());
+
+        assert_eq!(history.undo(third.clone()), Some(second.clone()));
+        assert_eq!(history.undo(second.clone()), Some(first.clone()));
+        assert_eq!(history.undo(first.clone()), None);
+
+        assert_eq!(history.redo(first.clone()), Some(second.clone()));
+        assert_eq!(history.redo(second), Some(third));
+        assert_eq!(history.redo(first), None);
+    }
+
+    #[test]
+    fn recording_clears_redo() {
+        let mut history = UndoHistory::new();
+        history.record(list_with(&["a"]));
+        history.undo(list_with(&["a", "b"]));
+
+        history.record(list_with(&["a"]));
+        assert_eq!(history.redo(list_with(&["a", "c"])), None);
+    }
+
+    #[test]
+    fn history_is_limited() {
+        let mut history = UndoHistory::new();
+        for _ in 0..HISTORY_LIMIT + 10 {
+            history.record(TodoList::new());
+        }
+
+        let mut undone = 0;
+        while history.undo(TodoList::new()).is_some() {
+            undone += 1;
+        }
+        assert_eq!(undone, HISTORY_LIMIT);
+    }
+
+    #[test]
+    fn describes_single_changes() {
+        let before = list_with(&["a"]);
+        let mut after = before.clone();
+        let id = after.add_todo("b".to_string(), None);
+        assert_eq!(describe_change(&before, &after), "restored \"b\"");
+        assert_eq!(describe_change(&after, &before), "removed \"b\"");
+
+        let mut edited = after.clone();
+        edited.get_todo_by_id_mut(&id).unwrap().title = "B".to_string();
+        assert_eq!(describe_change(&after, &edited), "changed \"B\"");
+    }
+
+    #[test]
+    fn clear_forgets_everything() {
+        let mut history = UndoHistory::new();
+        history.record(list_with(&["a"]));
+        history.undo(list_with(&["a", "b"]));
+        history.record(list_with(&["a", "c"]));
+
+        history.clear();
+        assert_eq!(history.undo(TodoList::new()), None);
+        assert_eq!(history.redo(TodoList::new()), None);
+    }
+
+    #[test]
+    fn summarizes_external_changes() {
+        let before = list_with(&["a", "b"]);
+        let mut after = before.clone();
+        after.add_todo("c".to_string(), None);
+        after.add_todo("d".to_string(), None);
+        let id = after.items[0].id;
+        after.toggle_todo(&id);
+
+        assert_eq!(summarize_changes(&before, &after), "2 todos added, 1 completed");
+        assert_eq!(summarize_changes(&before, &before), "Todo file reloaded");
+    }
+}
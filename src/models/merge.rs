@@ -0,0 +1,221 @@
+use crate::models::{TodoItem, TodoList};
+use serde_json::{Map, Value};
+use std::collections::HashMap;
+use uuid::Uuid;
+
+pub struct MergeResult {
+    pub list: TodoList,
+    pub conflicts: usize,
+}
+
+pub fn merge_lists(base: &TodoList, ours: &TodoList, theirs: &TodoList) -> MergeResult {
+    let base_items = item_values(base);
+    let our_items = item_values(ours);
+    let their_items = item_values(theirs);
+    let mut conflicts = 0;
+
+    let ids = ours.items.iter()
+        .chain(theirs.items.iter())
+        .chain(base.items.iter())
+        .map(|item| item.id)
+        .fold(Vec::new(), |mut ids, id| {
+            if !ids.contains(&id) {
+                ids.push(id);
+            }
+            ids
+        });
+
+    let mut items = Vec::new();
+    for id in ids {
+        let ours_is_latest = match (ours.get_todo_by_id(&id), theirs.get_todo_by_id(&id)) {
+            (Some(our_item), Some(their_item)) => our_item.last_changed() >= their_item.last_changed(),
+            _ => true,
+        };
+        let merged = merge_value(base_items.get(&id), our_items.get(&id), their_items.get(&id), ours_is_latest, &mut conflicts);
+        let Some(merged) = merged else { continue };
+
+        let item = serde_json::from_value::<TodoItem>(merged)
+            .ok()
+            .or_else(|| ours.get_todo_by_id(&id).cloned())
+            .or_else(|| theirs.get_todo_by_id(&id).cloned());
+
+        if let Some(mut item) = item {
+            item.expanded = ours.get_todo_by_id(&id).is_some_and(|ours| ours.expanded);
+            item.order = items.len() as u32;
+            items.push(item);
+        }
+    }
+
+    let workflow = if ours.workflow != base.workflow { ours.workflow.clone() } else { theirs.workflow.clone() };
+
+    let mut list = ours.clone();
+    list.items = items;
+    list.workflow = workflow;
+
+    let orphans: Vec<Uuid> = list.items.iter()
+        .filter(|item| item.parent_id.is_some_and(|parent| list.get_todo_by_id(&parent).is_none()))
+        .map(|item| item.id)
+        .collect();
+    for item in list.items.iter_mut().filter(|item| orphans.contains(&item.id)) {
+        item.parent_id = None;
+    }
+
+    list.normalize_statuses();
+    list.normalize_order();
+
+    MergeResult { list, conflicts }
+}
+
+fn item_values(list: &TodoList) -> HashMap<Uuid, Value> {
+    list.items.iter()
+        .filter_map(|item| serde_json::to_value(item).ok().map(|value| (item.id, value)))
+        .collect()
+}
+
+fn merge_value(base: Option<&Value>, ours: Option<&Value>, theirs: Option<&Value>, ours_is_latest: bool, conflicts: &mut usize) -> Option<Value> {
+    match (base, ours, theirs) {
+        (_, None, None) => None,
+        (None, Some(ours), _) => Some(ours.clone()),
+        (None, None, Some(theirs)) => Some(theirs.clone()),
+        (Some(base), Some(ours), None) => (ours != base).then(|| ours.clone()),
+        (Some(base), None, Some(theirs)) => (theirs != base).then(|| theirs.clone()),
+        (Some(base), Some(ours), Some(theirs)) => {
+            if ours == base {
+                Some(theirs.clone())
+            } else if theirs == base || ours == theirs {
+                Some(ours.clone())
+            } else {
+                Some(merge_fields(base, ours, theirs, ours_is_latest, conflicts))
+            }
+        }
+    }
+}
+
+fn merge_fields(base: &Value, ours: &Value, theirs: &Value, ours_is_latest: bool, conflicts: &mut usize) -> Value {
+    let (Some(base), Some(our_fields), Some(their_fields)) = (base.as_object(), ours.as_object(), theirs.as_object()) else {
+        *conflicts += 1;
+        return if ours_is_latest { ours.clone() } else { theirs.clone() };
+    };
+
+    let mut merged = Map::new();
+    for key in our_fields.keys().chain(their_fields.keys().filter(|key| !our_fields.contains_key(*key))) {
+        let (base_field, our_field, their_field) = (base.get(key), our_fields.get(key), their_fields.get(key));
+
+        let field = if our_field == base_field {
+            their_field
+        } else if their_field == base_field || our_field == their_field {
+            our_field
+        } else {
+            if key != "updated_at" {
+                *conflicts += 1;
+            }
+            if ours_is_latest { our_field } else { their_field }
+        };
+
+        if let Some(field) = field {
+            merged.insert(key.clone(), field.clone());
+        }
+    }
+    Value::Object(merged)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::models::Priority;
+    use chrono::{Duration, Utc};
+
+    fn base_list() -> (TodoList, Uuid, Uuid) {
+        let mut base = TodoList::new();
+        let first = base.add_todo("Write report".to_string(), None);
+        let second = base.add_todo("Book flights".to_string(), None);
+        (base, first, second)
+    }
+
+    fn edit(list: &TodoList, id: &Uuid, minutes: i64, change: impl FnOnce(&mut TodoItem)) -> TodoList {
+        let mut list = list.clone();
+        let item = list.get_todo_by_id_mut(id).unwrap();
+        change(item);
+        item.updated_at = Some(Utc::now() + Duration::minutes(minutes));
+        list
+    }
+
+    #[test]
+    fn combines_edits_to_different_fields() {
+        let (base, id, _) = base_list();
+        let ours = edit(&base, &id, 1, |item| item.title = "Write quarterly report".to_string());
+        let theirs = edit(&base, &id, 2, |item| item.priority = Priority::High);
+
+        let result = merge_lists(&base, &ours, &theirs);
+        let item = result.list.get_todo_by_id(&id).unwrap();
+        assert_eq!(item.title, "Write quarterly report");
+        assert_eq!(item.priority, Priority::High);
+        assert_eq!(result.conflicts, 0);
+    }
+
+    #[test]
+    fn conflicting_edits_keep_the_latest_change() {
+        let (base, id, _) = base_list();
+        let ours = edit(&base, &id, 2, |item| item.title = "Ours".to_string());
+        let theirs = edit(&base, &id, 1, |item| item.title = "Theirs".to_string());
+
+        let result = merge_lists(&base, &ours, &theirs);
+        assert_eq!(result.list.get_todo_by_id(&id).unwrap().title, "Ours");
+        assert_eq!(result.conflicts, 1);
+
+        let result = merge_lists(&base, &theirs, &ours);
+        assert_eq!(result.list.get_todo_by_id(&id).unwrap().title, "Ours");
+        assert_eq!(result.conflicts, 1);
+    }
+
+    #[test]
+    fn keeps_todos_added_on_both_sides() {
+        let (base, _, _) = base_list();
+        let mut ours = base.clone();
+        let our_id = ours.add_todo("Ours".to_string(), None);
+        let mut theirs = base.clone();
+        let their_id = theirs.add_todo("Theirs".to_string(), None);
+
+        let result = merge_lists(&base, &ours, &theirs);
+        assert_eq!(result.list.items.len(), 4);
+        assert!(result.list.get_todo_by_id(&our_id).is_some());
+        assert!(result.list.get_todo_by_id(&their_id).is_some());
+        assert_eq!(result.conflicts, 0);
+    }
+
+    #[test]
+    fn deletions_win_over_untouched_todos_only() {
+        let (base, first, second) = base_list();
+        let mut ours = base.clone();
+        ours.remove_todo(&first);
+        ours.remove_todo(&second);
+        let theirs = edit(&base, &second, 1, |item| item.title = "Book cheaper flights".to_string());
+
+        let result = merge_lists(&base, &ours, &theirs);
+        assert!(result.list.get_todo_by_id(&first).is_none());
+        assert_eq!(result.list.get_todo_by_id(&second).unwrap().title, "Book cheaper flights");
+    }
+
+    #[test]
+    fn clears_parents_deleted_on_the_other_side() {
+        let (base, parent, _) = base_list();
+        let mut ours = base.clone();
+        let child = ours.add_child_todo(&parent, "Collect numbers".to_string(), None);
+        let mut theirs = base.clone();
+        theirs.remove_todo(&parent);
+
+        let result = merge_lists(&base, &ours, &theirs);
+        assert!(result.list.get_todo_by_id(&parent).is_none());
+        assert_eq!(result.list.get_todo_by_id(&child).unwrap().parent_id, None);
+    }
+
+    #[test]
+    fn keeps_a_changed_workflow() {
+        let (base, _, _) = base_list();
+        let mut theirs = base.clone();
+        theirs.workflow = vec!["Open".to_string(), "Doing".to_string(), "Closed".to_string()];
+
+        let result = merge_lists(&base, &base, &theirs);
+        assert_eq!(result.list.workflow, theirs.workflow);
+    }
+}
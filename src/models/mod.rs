@@ -1,5 +1,17 @@
 pub mod config;
+pub mod due_date;
+pub mod fingerprint;
+pub mod history;
+pub mod merge;
+pub mod recurrence;
+pub mod time_tracking;
 pub mod todo;
 
 pub use config::*;
+pub use due_date::*;
+pub use fingerprint::*;
+pub use history::*;
+pub use merge::*;
+pub use recurrence::*;
+pub use time_tracking::*;
 pub use todo::*;
\ No newline at end of file
@@ -0,0 +1,207 @@
+use anyhow::{anyhow, Result};
+use chrono::{DateTime, Datelike, Days, Local, NaiveDate, NaiveTime, TimeZone, Utc, Weekday};
+use serde::{Deserialize, Serialize};
+
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+#[serde(tag = "kind", rename_all = "snake_case")]
+pub enum Recurrence {
+    Daily,
+    Weekdays,
+    Weekly { days: Vec<Weekday> },
+    Monthly { day: u32 },
+    AfterCompletion { days: u32 },
+}
+
+impl Recurrence {
+    pub fn parse(input: &str) -> Result<Option<Recurrence>> {
+        let input = input.trim().to_lowercase();
+        if input.is_empty() {
+            return Ok(None);
+        }
+
+        let today = Local::now().date_naive();
+        let (rule, rest) = input.split_once(' ').unwrap_or((input.as_str(), ""));
+        let rest = rest.trim();
+
+        let recurrence = match (rule, rest) {
+            ("daily", "") | ("every", "day") => Recurrence::Daily,
+            ("weekdays", "") | ("every", "weekday") => Recurrence::Weekdays,
+            ("weekly", "") => Recurrence::Weekly { days: vec![today.weekday()] },
+            ("weekly", days) | ("every", days) if parse_weekdays(days).is_some() => Recurrence::Weekly {
+                days: parse_weekdays(days).unwrap_or_default(),
+            },
+            ("monthly", "") => Recurrence::Monthly { day: today.day() },
+            ("monthly", day) => {
+                let day: u32 = day.trim_end_matches(|c: char| c.is_alphabetic()).parse()
+                    .map_err(|_| anyhow!("Invalid day of month: \"{}\"", day))?;
+                if !(1..=31).contains(&day) {
+                    return Err(anyhow!("Day of month must be between 1 and 31"));
+                }
+                Recurrence::Monthly { day }
+            }
+            ("every", interval) => Recurrence::AfterCompletion {
+                days: parse_interval_days(interval)
+                    .ok_or_else(|| anyhow!("Unrecognized repeat interval: \"{}\"", interval))?,
+            },
+            _ => return Err(anyhow!("Unrecognized repeat rule: \"{}\"", input)),
+        };
+
+        Ok(Some(recurrence))
+    }
+
+    pub fn describe(&self) -> String {
+        match self {
+            Recurrence::Daily => "daily".to_string(),
+            Recurrence::Weekdays => "weekdays".to_string(),
+            Recurrence::Weekly { days } => {
+                let days: Vec<String> = days.iter().map(|day| day.to_string().to_lowercase()).collect();
+                format!("weekly {}", days.join(","))
+            }
+            Recurrence::Monthly { day } => format!("monthly {}", day),
+            Recurrence::AfterCompletion { days } => format!("every {}d", days),
+        }
+    }
+
+    pub fn next_due(&self, previous_due: Option<&DateTime<Utc>>, completed_at: &DateTime<Utc>) -> Option<DateTime<Utc>> {
+        let completed_date = completed_at.with_timezone(&Local).date_naive();
+        let start = previous_due
+            .map(|due| due.with_timezone(&Local).date_naive())
+            .map_or(completed_date, |due_date| due_date.max(completed_date));
+
+        let next = match self {
+            Recurrence::Daily => start.checked_add_days(Days::new(1))?,
+            Recurrence::Weekdays => next_matching(start, |date| {
+                !matches!(date.weekday(), Weekday::Sat | Weekday::Sun)
+            })?,
+            Recurrence::Weekly { days } => next_matching(start, |date| days.contains(&date.weekday()))?,
+            Recurrence::Monthly { day } => next_matching(start, |date| date.day() == (*day).min(days_in_month(date)))?,
+            Recurrence::AfterCompletion { days } => completed_date.checked_add_days(Days::new(*days as u64))?,
+        };
+
+        Local
+            .from_local_datetime(&next.and_time(NaiveTime::MIN))
+            .earliest()
+            .map(|dt| dt.with_timezone(&Utc))
+    }
+}
+
+fn next_matching(start: NaiveDate, matches: impl Fn(NaiveDate) -> bool) -> Option<NaiveDate> {
+    (1..=366)
+        .map_while(|offset| start.checked_add_days(Days::new(offset)))
+        .find(|date| matches(*date))
+}
+
+fn days_in_month(date: NaiveDate) -> u32 {
+    let (year, month) = if date.month() == 12 {
+        (date.year() + 1, 1)
+    } else {
+        (date.year(), date.month() + 1)
+    };
+    NaiveDate::from_ymd_opt(year, month, 1)
+        .and_then(|first| first.pred_opt())
+        .map_or(31, |last| last.day())
+}
+
+fn parse_weekdays(input: &str) -> Option<Vec<Weekday>> {
+    let mut days = Vec::new();
+    for day in input.split(|c: char| c == ',' || c.is_whitespace()).filter(|day| !day.is_empty()) {
+        let weekday: Weekday = day.parse().ok()?;
+        if !days.contains(&weekday) {
+            days.push(weekday);
+        }
+    }
+
+    if days.is_empty() {
+        None
+    } else {
+        days.sort_by_key(|day| day.num_days_from_monday());
+        Some(days)
+    }
+}
+
+fn parse_interval_days(input: &str) -> Option<u32> {
+    let input = input.replace(' ', "");
+    let split = input.find(|c: char| !c.is_ascii_digit())?;
+    let (amount, unit) = input.split_at(split);
+    let amount: u32 = amount.parse().ok()?;
+
+    let days = match unit {
+        "d" | "day" | "days" => amount,
+        "w" | "wk" | "week" | "weeks" => amount.checked_mul(7)?,
+        _ => return None,
+    };
+
+    if days == 0 { None } else { Some(days) }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn local(year: i32, month: u32, day: u32) -> DateTime<Utc> {
+        Local.with_ymd_and_hms(year, month, day, 0, 0, 0).unwrap().with_timezone(&Utc)
+    }
+
+    fn next_date(recurrence: &Recurrence, previous_due: Option<DateTime<Utc>>, completed_at: DateTime<Utc>) -> Option<NaiveDate> {
+        recurrence.next_due(previous_due.as_ref(), &completed_at)
+            .map(|due| due.with_timezone(&Local).date_naive())
+    }
+
+    fn date(year: i32, month: u32, day: u32) -> NaiveDate {
+        NaiveDate::from_ymd_opt(year, month, day).unwrap()
+    }
+
+    #[test]
+    fn daily_follows_the_later_of_due_and_completion() {
+        let rule = Recurrence::Daily;
+        assert_eq!(next_date(&rule, Some(local(2024, 3, 10)), local(2024, 3, 13)), Some(date(2024, 3, 14)));
+        assert_eq!(next_date(&rule, Some(local(2024, 3, 20)), local(2024, 3, 13)), Some(date(2024, 3, 21)));
+    }
+
+    #[test]
+    fn weekdays_skip_the_weekend() {
+        assert_eq!(next_date(&Recurrence::Weekdays, None, local(2024, 3, 15)), Some(date(2024, 3, 18)));
+    }
+
+    #[test]
+    fn weekly_picks_the_next_listed_day() {
+        let rule = Recurrence::Weekly { days: vec![Weekday::Mon, Weekday::Thu] };
+        assert_eq!(next_date(&rule, None, local(2024, 3, 12)), Some(date(2024, 3, 14)));
+        assert_eq!(next_date(&rule, None, local(2024, 3, 14)), Some(date(2024, 3, 18)));
+    }
+
+    #[test]
+    fn monthly_clamps_to_short_months() {
+        let rule = Recurrence::Monthly { day: 31 };
+        assert_eq!(next_date(&rule, Some(local(2024, 1, 31)), local(2024, 1, 31)), Some(date(2024, 2, 29)));
+    }
+
+    #[test]
+    fn after_completion_counts_from_completion() {
+        let rule = Recurrence::AfterCompletion { days: 10 };
+        assert_eq!(next_date(&rule, Some(local(2024, 3, 1)), local(2024, 3, 5)), Some(date(2024, 3, 15)));
+    }
+
+    #[test]
+    fn huge_intervals_have_no_next_occurrence() {
+        let rule = Recurrence::AfterCompletion { days: u32::MAX };
+        assert_eq!(next_date(&rule, None, local(2024, 3, 5)), None);
+    }
+
+    #[test]
+    fn parses_rules() {
+        assert_eq!(Recurrence::parse("daily").unwrap(), Some(Recurrence::Daily));
+        assert_eq!(Recurrence::parse("every mon, fri").unwrap(), Some(Recurrence::Weekly { days: vec![Weekday::Mon, Weekday::Fri] }));
+        assert_eq!(Recurrence::parse("monthly 15th").unwrap(), Some(Recurrence::Monthly { day: 15 }));
+        assert_eq!(Recurrence::parse("every 2w").unwrap(), Some(Recurrence::AfterCompletion { days: 14 }));
+        assert_eq!(Recurrence::parse("").unwrap(), None);
+    }
+
+    #[test]
+    fn rejects_bad_rules() {
+        assert!(Recurrence::parse("monthly 32").is_err());
+        assert!(Recurrence::parse("every 0d").is_err());
+        assert!(Recurrence::parse("every 999999999w").is_err());
+        assert!(Recurrence::parse("fortnightly").is_err());
+    }
+}
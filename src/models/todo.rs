@@ -1,22 +1,122 @@
-use chrono::{DateTime, Local, Utc};
+use chrono::{DateTime, Duration, Local, Utc};
 use serde::{Deserialize, Serialize};
+use serde_json::Value;
+use std::collections::{BTreeMap, HashMap};
 use uuid::Uuid;
 use crate::models::config::DateFormat;
+use crate::models::due_date::days_until_due;
+use crate::models::recurrence::Recurrence;
+use crate::models::time_tracking::TimeEntry;
 
-const CURRENT_VERSION: u32 = 1;
+pub const CURRENT_VERSION: u32 = 2;
+
+pub const DEFAULT_WORKFLOW: [&str; 4] = ["Todo", "In Progress", "Review", "Done"];
 
 fn default_version() -> u32 {
     CURRENT_VERSION
 }
 
+pub fn default_workflow() -> Vec<String> {
+    DEFAULT_WORKFLOW.iter().map(|state| state.to_string()).collect()
+}
+
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq, PartialOrd, Ord, Default)]
+pub enum Priority {
+    #[default]
+    None,
+    Low,
+    Medium,
+    High,
+    Urgent,
+}
+
+impl Priority {
+    pub fn name(&self) -> &'static str {
+        match self {
+            Priority::None => "None",
+            Priority::Low => "Low",
+            Priority::Medium => "Medium",
+            Priority::High => "High",
+            Priority::Urgent => "Urgent",
+        }
+    }
+
+    pub fn marker(&self) -> &'static str {
+        match self {
+            Priority::None => "",
+            Priority::Low => "↓ ",
+            Priority::Medium => "! ",
+            Priority::High => "!! ",
+            Priority::Urgent => "!!! ",
+        }
+    }
+
+    pub fn raise(&self) -> Priority {
+        match self {
+            Priority::None => Priority::Low,
+            Priority::Low => Priority::Medium,
+            Priority::Medium => Priority::High,
+            Priority::High | Priority::Urgent => Priority::Urgent,
+        }
+    }
+
+    pub fn lower(&self) -> Priority {
+        match self {
+            Priority::None | Priority::Low => Priority::None,
+            Priority::Medium => Priority::Low,
+            Priority::High => Priority::Medium,
+            Priority::Urgent => Priority::High,
+        }
+    }
+
+    pub fn is_none(&self) -> bool {
+        matches!(self, Priority::None)
+    }
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct CommitLink {
+    pub hash: String,
+    pub summary: String,
+}
+
+impl CommitLink {
+    pub fn short_hash(&self) -> &str {
+        &self.hash[..self.hash.len().min(7)]
+    }
+}
+
 #[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
 pub struct TodoItem {
     pub id: Uuid,
     pub title: String,
     pub description: Option<String>,
-    pub completed: bool,
+    #[serde(default)]
+    pub status: String,
     pub created_at: DateTime<Utc>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub updated_at: Option<DateTime<Utc>>,
     pub completed_at: Option<DateTime<Utc>>,
+    #[serde(default, skip_serializing_if = "Priority::is_none")]
+    pub priority: Priority,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub due_at: Option<DateTime<Utc>>,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub tags: Vec<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub parent_id: Option<Uuid>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub recurrence: Option<Recurrence>,
+    // Saved files keep items in list order, so the position is implied rather than written;
+    // renumbering after a move would otherwise touch every record. Older files still carry it.
+    #[serde(default, skip_serializing)]
+    pub order: u32,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub time_entries: Vec<TimeEntry>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub closed_by: Option<CommitLink>,
+    #[serde(default, skip_serializing_if = "BTreeMap::is_empty")]
+    pub extensions: BTreeMap<String, Value>,
     #[serde(skip)]
     pub expanded: bool,
 }
@@ -27,35 +127,78 @@ impl TodoItem {
             id: Uuid::new_v4(),
             title,
             description,
-            completed: false,
+            status: DEFAULT_WORKFLOW[0].to_string(),
             created_at: Utc::now(),
+            updated_at: None,
             completed_at: None,
+            priority: Priority::None,
+            due_at: None,
+            tags: Vec::new(),
+            parent_id: None,
+            recurrence: None,
+            order: 0,
+            time_entries: Vec::new(),
+            closed_by: None,
+            extensions: BTreeMap::new(),
             expanded: false,
         }
     }
 
-    pub fn toggle_completed(&mut self) {
-        self.completed = !self.completed;
-        if self.completed {
-            self.completed_at = Some(Utc::now());
-        } else {
-            self.completed_at = None;
-        }
-    }
-
     pub fn toggle_expanded(&mut self) {
         self.expanded = !self.expanded;
     }
 
+    pub fn is_completed(&self) -> bool {
+        self.completed_at.is_some()
+    }
+
+    pub fn last_changed(&self) -> DateTime<Utc> {
+        self.updated_at.unwrap_or(self.created_at)
+    }
+
     pub fn has_description(&self) -> bool {
         self.description.as_ref().map_or(false, |desc| !desc.trim().is_empty())
     }
+
+    pub fn has_tag(&self, tag: &str) -> bool {
+        self.tags.iter().any(|t| t.eq_ignore_ascii_case(tag))
+    }
+
+    pub fn is_overdue(&self) -> bool {
+        !self.is_completed() && self.due_at.as_ref().is_some_and(|due_at| days_until_due(due_at) < 0)
+    }
+
+    pub fn running_entry(&self) -> Option<&TimeEntry> {
+        self.time_entries.iter().find(|entry| entry.is_running())
+    }
+
+    pub fn is_timer_running(&self) -> bool {
+        self.running_entry().is_some()
+    }
+
+    pub fn stop_timer(&mut self) -> Option<Duration> {
+        let entry = self.time_entries.iter_mut().find(|entry| entry.is_running())?;
+        entry.end = Some(Utc::now());
+        Some(entry.duration())
+    }
+
+    pub fn total_time_spent(&self) -> Duration {
+        self.time_entries.iter().fold(Duration::zero(), |total, entry| total + entry.duration())
+    }
+}
+
+enum StatusChange {
+    Rejected,
+    Changed,
+    Spawned(Uuid),
 }
 
-#[derive(Debug, Clone, Serialize, Deserialize, Default)]
+#[derive(Debug, Clone, Serialize, Deserialize, Default, PartialEq)]
 pub struct TodoList {
     #[serde(default = "default_version")]
     pub version: u32,
+    #[serde(default = "default_workflow")]
+    pub workflow: Vec<String>,
     pub items: Vec<TodoItem>,
 }
 
@@ -63,33 +206,236 @@ impl TodoList {
     pub fn new() -> Self {
         Self {
             version: CURRENT_VERSION,
+            workflow: default_workflow(),
             items: Vec::new()
         }
     }
 
-    pub fn add_todo(&mut self, title: String, description: Option<String>) {
-        let todo = TodoItem::new(title, description);
-        self.items.push(todo);
+    pub fn initial_state(&self) -> &str {
+        self.workflow.first().map_or(DEFAULT_WORKFLOW[0], |state| state.as_str())
+    }
+
+    pub fn final_state(&self) -> &str {
+        self.workflow.last().map_or(DEFAULT_WORKFLOW[3], |state| state.as_str())
+    }
+
+    pub fn state_index(&self, status: &str) -> Option<usize> {
+        self.workflow.iter().position(|state| state == status)
+    }
+
+    pub fn normalize_statuses(&mut self) {
+        if self.workflow.len() < 2 {
+            self.workflow = default_workflow();
+        }
+
+        let initial = self.initial_state().to_string();
+        let final_state = self.final_state().to_string();
+        for item in self.items.iter_mut() {
+            if !self.workflow.contains(&item.status) {
+                item.status = if item.is_completed() { final_state.clone() } else { initial.clone() };
+            }
+
+            if item.status == final_state && item.completed_at.is_none() {
+                item.completed_at = Some(Utc::now());
+            } else if item.status != final_state {
+                item.completed_at = None;
+            }
+        }
+    }
+
+    pub fn add_todo(&mut self, title: String, description: Option<String>) -> Uuid {
+        self.push_item(TodoItem::new(title, description))
+    }
+
+    pub fn add_child_todo(&mut self, parent_id: &Uuid, title: String, description: Option<String>) -> Uuid {
+        let mut todo = TodoItem::new(title, description);
+        todo.parent_id = Some(*parent_id);
+        self.push_item(todo)
+    }
+
+    fn push_item(&mut self, mut item: TodoItem) -> Uuid {
+        item.status = self.initial_state().to_string();
+        item.order = self.items.iter().map(|item| item.order + 1).max().unwrap_or(0);
+        let id = item.id;
+        self.items.push(item);
+        id
+    }
+
+    pub fn stamp_changes(&mut self, previous: &TodoList) {
+        let now = Utc::now();
+        for item in self.items.iter_mut() {
+            let Some(before) = previous.get_todo_by_id(&item.id) else { continue };
+            let mut before = before.clone();
+            before.updated_at = item.updated_at;
+            before.expanded = item.expanded;
+            before.order = item.order;
+            if before != *item {
+                item.updated_at = Some(now);
+            }
+        }
+    }
+
+    pub fn normalize_order(&mut self) {
+        self.items.sort_by_key(|item| item.order);
+        for (index, item) in self.items.iter_mut().enumerate() {
+            item.order = index as u32;
+        }
+    }
+
+    pub fn move_todo(&mut self, id: &Uuid, target_id: &Uuid, after: bool) -> bool {
+        if id == target_id || self.get_todo_by_id(target_id).is_none() {
+            return false;
+        }
+
+        if let Some(from) = self.items.iter().position(|item| &item.id == id) {
+            let item = self.items.remove(from);
+            let target = self.items.iter().position(|item| &item.id == target_id).unwrap_or(self.items.len());
+            self.items.insert(if after { target + 1 } else { target }, item);
+
+            for (index, item) in self.items.iter_mut().enumerate() {
+                item.order = index as u32;
+            }
+            true
+        } else {
+            false
+        }
     }
 
     pub fn remove_todo(&mut self, id: &Uuid) -> bool {
         if let Some(index) = self.items.iter().position(|item| &item.id == id) {
-            self.items.remove(index);
+            let removed = self.items.remove(index);
+            for item in self.items.iter_mut().filter(|item| item.parent_id == Some(removed.id)) {
+                item.parent_id = removed.parent_id;
+            }
             true
         } else {
             false
         }
     }
 
-    pub fn toggle_todo(&mut self, id: &Uuid) -> bool {
+    pub fn set_parent(&mut self, id: &Uuid, parent_id: Option<Uuid>) -> bool {
+        if let Some(parent_id) = parent_id {
+            if &parent_id == id || self.is_descendant_of(&parent_id, id) {
+                return false;
+            }
+        }
+
         if let Some(item) = self.items.iter_mut().find(|item| &item.id == id) {
-            item.toggle_completed();
+            item.parent_id = parent_id;
             true
         } else {
             false
         }
     }
 
+    pub fn is_descendant_of(&self, id: &Uuid, ancestor_id: &Uuid) -> bool {
+        let mut current = self.get_todo_by_id(id).and_then(|item| item.parent_id);
+        while let Some(parent_id) = current {
+            if &parent_id == ancestor_id {
+                return true;
+            }
+            current = self.get_todo_by_id(&parent_id).and_then(|item| item.parent_id);
+        }
+        false
+    }
+
+    pub fn get_children(&self, id: &Uuid) -> Vec<&TodoItem> {
+        self.items.iter().filter(|item| item.parent_id.as_ref() == Some(id)).collect()
+    }
+
+    pub fn get_descendant_ids(&self, id: &Uuid) -> Vec<Uuid> {
+        let mut descendants = Vec::new();
+        let mut pending = vec![*id];
+        while let Some(current) = pending.pop() {
+            for child in self.get_children(&current) {
+                descendants.push(child.id);
+                pending.push(child.id);
+            }
+        }
+        descendants
+    }
+
+    /// `(completed, total)` children for every todo that has any, in one pass over the list.
+    pub fn child_progress(&self) -> HashMap<Uuid, (usize, usize)> {
+        let mut progress: HashMap<Uuid, (usize, usize)> = HashMap::new();
+        for item in &self.items {
+            if let Some(parent_id) = item.parent_id {
+                let (completed, total) = progress.entry(parent_id).or_default();
+                *completed += usize::from(item.is_completed());
+                *total += 1;
+            }
+        }
+        progress
+    }
+
+    /// Returns the id of the next occurrence when this completes a recurring todo.
+    pub fn toggle_todo(&mut self, id: &Uuid) -> Option<Uuid> {
+        let status = match self.get_todo_by_id(id) {
+            Some(item) if item.is_completed() => self.initial_state().to_string(),
+            Some(_) => self.final_state().to_string(),
+            None => return None,
+        };
+        match self.change_status(id, &status) {
+            StatusChange::Spawned(next_id) => Some(next_id),
+            StatusChange::Rejected | StatusChange::Changed => None,
+        }
+    }
+
+    pub fn set_status(&mut self, id: &Uuid, status: &str) -> bool {
+        !matches!(self.change_status(id, status), StatusChange::Rejected)
+    }
+
+    fn change_status(&mut self, id: &Uuid, status: &str) -> StatusChange {
+        if self.state_index(status).is_none() {
+            return StatusChange::Rejected;
+        }
+
+        let completes = status == self.final_state();
+        if let Some(item) = self.items.iter_mut().find(|item| &item.id == id) {
+            let was_completed = item.is_completed();
+            item.status = status.to_string();
+
+            if completes && !was_completed {
+                item.completed_at = Some(Utc::now());
+                item.stop_timer();
+                return match self.spawn_next_occurrence(id) {
+                    Some(next_id) => StatusChange::Spawned(next_id),
+                    None => StatusChange::Changed,
+                };
+            } else if !completes {
+                item.completed_at = None;
+                item.closed_by = None;
+            }
+            StatusChange::Changed
+        } else {
+            StatusChange::Rejected
+        }
+    }
+
+    pub fn shift_status(&mut self, id: &Uuid, forward: bool) -> Option<String> {
+        let index = self.state_index(&self.get_todo_by_id(id)?.status)?;
+        let next = if forward { index + 1 } else { index.checked_sub(1)? };
+        let status = self.workflow.get(next)?.clone();
+        self.set_status(id, &status);
+        Some(status)
+    }
+
+    fn spawn_next_occurrence(&mut self, id: &Uuid) -> Option<Uuid> {
+        let item = self.items.iter_mut().find(|item| &item.id == id)?;
+        let completed_at = item.completed_at?;
+        let due_at = item.recurrence.as_ref()?.next_due(item.due_at.as_ref(), &completed_at)?;
+        let recurrence = item.recurrence.take()?;
+
+        let mut next = TodoItem::new(item.title.clone(), item.description.clone());
+        next.priority = item.priority;
+        next.tags = item.tags.clone();
+        next.parent_id = item.parent_id;
+        next.due_at = Some(due_at);
+        next.recurrence = Some(recurrence);
+
+        Some(self.push_item(next))
+    }
+
     pub fn toggle_expanded(&mut self, id: &Uuid) -> bool {
         if let Some(item) = self.items.iter_mut().find(|item| &item.id == id) {
             item.toggle_expanded();
@@ -108,12 +454,61 @@ impl TodoList {
         }
     }
 
+    pub fn set_priority(&mut self, id: &Uuid, priority: Priority) -> bool {
+        if let Some(item) = self.items.iter_mut().find(|item| &item.id == id) {
+            item.priority = priority;
+            true
+        } else {
+            false
+        }
+    }
+
+    pub fn get_running_timer(&self) -> Option<&TodoItem> {
+        self.items.iter().find(|item| item.is_timer_running())
+    }
+
+    pub fn start_timer(&mut self, id: &Uuid) -> bool {
+        if self.get_todo_by_id(id).is_none() {
+            return false;
+        }
+
+        self.stop_all_timers();
+        if let Some(item) = self.items.iter_mut().find(|item| &item.id == id) {
+            item.time_entries.push(TimeEntry::start_now());
+        }
+        true
+    }
+
+    pub fn stop_timer(&mut self, id: &Uuid) -> Option<Duration> {
+        self.items.iter_mut().find(|item| &item.id == id)?.stop_timer()
+    }
+
+    pub fn stop_all_timers(&mut self) {
+        for item in self.items.iter_mut() {
+            item.stop_timer();
+        }
+    }
+
     pub fn get_active_todos(&self) -> Vec<&TodoItem> {
-        self.items.iter().filter(|item| !item.completed).collect()
+        self.items.iter().filter(|item| !item.is_completed()).collect()
     }
 
     pub fn get_completed_todos(&self) -> Vec<&TodoItem> {
-        self.items.iter().filter(|item| item.completed).collect()
+        self.items.iter().filter(|item| item.is_completed()).collect()
+    }
+
+    pub fn get_todos_by_status(&self, status: &str) -> Vec<&TodoItem> {
+        self.items.iter().filter(|item| item.status == status).collect()
+    }
+
+    pub fn get_tag_counts(&self) -> Vec<(String, usize)> {
+        let mut counts: BTreeMap<String, (String, usize)> = BTreeMap::new();
+        for tag in self.items.iter().flat_map(|item| item.tags.iter()) {
+            counts.entry(tag.to_lowercase())
+                .or_insert_with(|| (tag.clone(), 0))
+                .1 += 1;
+        }
+        counts.into_values().collect()
     }
 
     pub fn get_todo_by_id(&self, id: &Uuid) -> Option<&TodoItem> {
@@ -125,6 +520,21 @@ impl TodoList {
     }
 }
 
+pub fn parse_tags(input: &str) -> Vec<String> {
+    let mut tags: Vec<String> = Vec::new();
+    for tag in input.split(|c: char| c == ',' || c.is_whitespace()) {
+        let tag = tag.trim().trim_start_matches('#');
+        if !tag.is_empty() && !tags.iter().any(|t| t.eq_ignore_ascii_case(tag)) {
+            tags.push(tag.to_string());
+        }
+    }
+    tags
+}
+
+pub fn format_tags(tags: &[String]) -> String {
+    tags.join(", ")
+}
+
 pub fn format_datetime(dt: &DateTime<Utc>, format: &DateFormat) -> String {
     let local_dt = dt.with_timezone(&Local);
 
@@ -151,4 +561,155 @@ pub fn format_datetime(dt: &DateTime<Utc>, format: &DateFormat) -> String {
             }
         }
     }
-}
\ No newline at end of file
+}
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn new_todos_start_in_the_first_state() {
+        let mut todos = TodoList::new();
+        let id = todos.add_todo("Write tests".to_string(), None);
+        let item = todos.get_todo_by_id(&id).unwrap();
+        assert_eq!(item.status, "Todo");
+        assert!(!item.is_completed());
+    }
+
+    #[test]
+    fn only_the_final_state_completes() {
+        let mut todos = TodoList::new();
+        let id = todos.add_todo("Write tests".to_string(), None);
+
+        assert!(todos.set_status(&id, "Review"));
+        assert!(!todos.get_todo_by_id(&id).unwrap().is_completed());
+
+        assert!(todos.set_status(&id, "Done"));
+        assert!(todos.get_todo_by_id(&id).unwrap().is_completed());
+
+        assert!(todos.set_status(&id, "In Progress"));
+        assert!(todos.get_todo_by_id(&id).unwrap().completed_at.is_none());
+    }
+
+    #[test]
+    fn unknown_states_are_rejected() {
+        let mut todos = TodoList::new();
+        let id = todos.add_todo("Write tests".to_string(), None);
+        assert!(!todos.set_status(&id, "Blocked"));
+        assert_eq!(todos.get_todo_by_id(&id).unwrap().status, "Todo");
+    }
+
+    #[test]
+    fn shift_status_stops_at_the_ends() {
+        let mut todos = TodoList::new();
+        let id = todos.add_todo("Write tests".to_string(), None);
+
+        assert_eq!(todos.shift_status(&id, false), None);
+        assert_eq!(todos.shift_status(&id, true).as_deref(), Some("In Progress"));
+        todos.set_status(&id, "Done");
+        assert_eq!(todos.shift_status(&id, true), None);
+    }
+
+    #[test]
+    fn toggle_jumps_between_first_and_final_state() {
+        let mut todos = TodoList::new();
+        let id = todos.add_todo("Write tests".to_string(), None);
+        todos.set_status(&id, "Review");
+
+        todos.toggle_todo(&id);
+        assert_eq!(todos.get_todo_by_id(&id).unwrap().status, "Done");
+        todos.toggle_todo(&id);
+        assert_eq!(todos.get_todo_by_id(&id).unwrap().status, "Todo");
+    }
+
+    #[test]
+    fn normalize_statuses_maps_unknown_states() {
+        let mut todos = TodoList::new();
+        let open = todos.add_todo("Open".to_string(), None);
+        let done = todos.add_todo("Done".to_string(), None);
+        todos.get_todo_by_id_mut(&open).unwrap().status = "Someday".to_string();
+        let item = todos.get_todo_by_id_mut(&done).unwrap();
+        item.status = "Shipped".to_string();
+        item.completed_at = Some(Utc::now());
+
+        todos.normalize_statuses();
+        assert_eq!(todos.get_todo_by_id(&open).unwrap().status, "Todo");
+        assert_eq!(todos.get_todo_by_id(&done).unwrap().status, "Done");
+    }
+
+    #[test]
+    fn custom_workflows_complete_in_their_last_state() {
+        let mut todos = TodoList::new();
+        todos.workflow = vec!["Backlog".to_string(), "Shipped".to_string()];
+        let id = todos.add_todo("Release".to_string(), None);
+        assert_eq!(todos.get_todo_by_id(&id).unwrap().status, "Backlog");
+
+        todos.set_status(&id, "Shipped");
+        assert!(todos.get_todo_by_id(&id).unwrap().is_completed());
+        assert_eq!(todos.get_todos_by_status("Shipped").len(), 1);
+    }
+
+    #[test]
+    fn completing_a_recurring_todo_spawns_the_next_one() {
+        let mut todos = TodoList::new();
+        let id = todos.add_todo("Water plants".to_string(), None);
+        todos.get_todo_by_id_mut(&id).unwrap().recurrence = Some(Recurrence::Daily);
+
+        todos.set_status(&id, "Done");
+        assert_eq!(todos.items.len(), 2);
+        let next = todos.items.iter().find(|item| item.id != id).unwrap();
+        assert_eq!(next.status, "Todo");
+        assert_eq!(next.recurrence, Some(Recurrence::Daily));
+        assert!(next.due_at.is_some());
+        assert!(todos.get_todo_by_id(&id).unwrap().recurrence.is_none());
+    }
+
+    #[test]
+    fn toggle_returns_the_next_occurrence() {
+        let mut todos = TodoList::new();
+        let id = todos.add_todo("Water plants".to_string(), None);
+        let plain = todos.add_todo("Buy soil".to_string(), None);
+        todos.get_todo_by_id_mut(&id).unwrap().recurrence = Some(Recurrence::Daily);
+
+        let next = todos.toggle_todo(&id).unwrap();
+        assert_eq!(todos.get_todo_by_id(&next).unwrap().recurrence, Some(Recurrence::Daily));
+        assert_eq!(todos.toggle_todo(&plain), None);
+        assert_eq!(todos.toggle_todo(&id), None);
+    }
+
+    #[test]
+    fn child_progress_counts_direct_children() {
+        let mut todos = TodoList::new();
+        let parent = todos.add_todo("Release".to_string(), None);
+        let notes = todos.add_todo("Notes".to_string(), None);
+        let tag = todos.add_todo("Tag".to_string(), None);
+        let nested = todos.add_todo("Proofread".to_string(), None);
+        todos.get_todo_by_id_mut(&notes).unwrap().parent_id = Some(parent);
+        todos.get_todo_by_id_mut(&tag).unwrap().parent_id = Some(parent);
+        todos.get_todo_by_id_mut(&nested).unwrap().parent_id = Some(notes);
+        todos.toggle_todo(&tag);
+
+        let progress = todos.child_progress();
+        assert_eq!(progress.get(&parent), Some(&(1, 2)));
+        assert_eq!(progress.get(&notes), Some(&(0, 1)));
+        assert_eq!(progress.get(&tag), None);
+    }
+
+    #[test]
+    fn moving_a_todo_leaves_the_others_untouched() {
+        let mut todos = TodoList::new();
+        let first = todos.add_todo("Write report".to_string(), None);
+        todos.add_todo("Book flights".to_string(), None);
+        let last = todos.add_todo("Pack".to_string(), None);
+        let before = todos.clone();
+
+        assert!(todos.move_todo(&last, &first, false));
+        todos.stamp_changes(&before);
+        assert!(todos.items.iter().all(|item| item.updated_at.is_none()));
+
+        let saved = serde_json::to_string(&todos).unwrap();
+        assert!(!saved.contains("\"order\""));
+        let mut loaded: TodoList = serde_json::from_str(&saved).unwrap();
+        loaded.normalize_order();
+        assert_eq!(loaded.items, todos.items);
+    }
+}
@@ -110,7 +110,7 @@ impl ProjectInitializer {
 
     pub fn create_todo_file(repo: &GitRepository, choice: TodoStorageChoice) -> Result<std::path::PathBuf, anyhow::Error> {
         use crate::models::TodoList;
-        use crate::storage::JsonStore;
+        use crate::storage::{JsonStore, TodoStore};
 
         let todo_path = match choice {
             TodoStorageChoice::Project => repo.get_project_todo_path(),
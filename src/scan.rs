@@ -0,0 +1,321 @@
+use crate::git::GitRepository;
+use crate::models::{fnv1a, TodoItem, TodoList};
+use anyhow::{Context, Result};
+use serde_json::{json, Value};
+use std::collections::{HashMap, HashSet};
+use std::fs;
+use std::path::{Path, PathBuf};
+use uuid::Uuid;
+
+pub const EXTENSION_KEY: &str = "scan";
+const MARKERS: [&str; 3] = ["TODO", "FIXME", "HACK"];
+const COMMENT_LEADERS: [&str; 7] = ["//", "#", "/*", "<!--", "--", ";", "%"];
+const COMMENT_CLOSERS: [&str; 2] = ["*/", "-->"];
+const MAX_FILE_SIZE: u64 = 1024 * 1024;
+
+pub struct CodeComment {
+    pub file: String,
+    pub line: usize,
+    pub kind: &'static str,
+    pub text: String,
+    pub author: Option<String>,
+    pub fingerprint: String,
+}
+
+impl CodeComment {
+    pub fn location(&self) -> String {
+        format!("{}:{}", self.file, self.line)
+    }
+
+    fn description(&self) -> String {
+        match &self.author {
+            Some(author) => format!("Found at `{}` (written by {})", self.location(), author),
+            None => format!("Found at `{}`", self.location()),
+        }
+    }
+
+    fn extension(&self) -> Value {
+        let mut extension = json!({
+            "fingerprint": self.fingerprint,
+            "file": self.file,
+            "line": self.line,
+            "kind": self.kind,
+        });
+        if let Some(author) = &self.author {
+            extension["author"] = json!(author);
+        }
+        extension
+    }
+}
+
+#[derive(Debug, Default)]
+pub struct ScanSummary {
+    pub added: usize,
+    pub updated: usize,
+    pub completed: usize,
+    pub reopened: usize,
+}
+
+pub fn scan_repository(repo: &GitRepository, skip: &[PathBuf]) -> Result<Vec<CodeComment>> {
+    let files = repo.list_files().context("Failed to list the repository's files")?;
+    let skip: Vec<PathBuf> = skip.iter().filter_map(|path| fs::canonicalize(path).ok()).collect();
+
+    let mut comments = Vec::new();
+    for file in files {
+        let path = repo.root.join(&file);
+        if fs::canonicalize(&path).is_ok_and(|path| skip.contains(&path)) {
+            continue;
+        }
+        if fs::metadata(&path).map_or(true, |metadata| !metadata.is_file() || metadata.len() > MAX_FILE_SIZE) {
+            continue;
+        }
+        let Ok(content) = fs::read_to_string(&path) else { continue };
+
+        let mut found = find_comments(&file, &content);
+        if found.iter().any(|comment| comment.author.is_none()) {
+            let authors = repo.blame_authors(&file).unwrap_or_default();
+            for comment in found.iter_mut().filter(|comment| comment.author.is_none()) {
+                comment.author = authors.get(&comment.line).cloned();
+            }
+        }
+        comments.extend(found);
+    }
+
+    Ok(comments)
+}
+
+pub fn find_comments(file: &Path, content: &str) -> Vec<CodeComment> {
+    let file = file.to_string_lossy().replace('\\', "/");
+    let mut occurrences: HashMap<(&str, String), usize> = HashMap::new();
+    let mut comments = Vec::new();
+
+    for (index, line) in content.lines().enumerate() {
+        let Some((kind, author, text)) = parse_comment(line) else { continue };
+
+        let occurrence = occurrences.entry((kind, text.clone())).or_insert(0);
+        *occurrence += 1;
+        let fingerprint = format!("{:016x}", fnv1a(&[file.as_str(), kind, &text, &occurrence.to_string()]));
+
+        comments.push(CodeComment { file: file.clone(), line: index + 1, kind, text, author, fingerprint });
+    }
+
+    comments
+}
+
+pub fn sync(todo_list: &mut TodoList, comments: &[CodeComment]) -> ScanSummary {
+    let mut summary = ScanSummary::default();
+    let mut matched: HashSet<Uuid> = HashSet::new();
+
+    let mut links: Vec<Option<Uuid>> = Vec::new();
+    for comment in comments {
+        let linked = todo_list.items.iter()
+            .find(|item| !matched.contains(&item.id) && scan_field(item, "fingerprint") == Some(&comment.fingerprint))
+            .map(|item| item.id);
+        matched.extend(linked);
+        links.push(linked);
+    }
+
+    for (comment, link) in comments.iter().zip(links.iter_mut()).filter(|(_, link)| link.is_none()) {
+        *link = todo_list.items.iter()
+            .filter(|item| {
+                !matched.contains(&item.id)
+                    && !item.is_completed()
+                    && scan_field(item, "file") == Some(&comment.file)
+                    && scan_field(item, "kind") == Some(comment.kind)
+            })
+            .min_by_key(|item| scan_line(item).unwrap_or(0).abs_diff(comment.line))
+            .map(|item| item.id);
+        matched.extend(*link);
+    }
+
+    for (comment, linked) in comments.iter().zip(links) {
+        let id = match linked {
+            Some(id) => id,
+            None => {
+                let id = todo_list.add_todo(comment.text.clone(), Some(comment.description()));
+                summary.added += 1;
+                id
+            }
+        };
+        matched.insert(id);
+
+        let was_missing = todo_list.get_todo_by_id(&id)
+            .is_some_and(|item| item.is_completed() && scan_missing(item));
+        if was_missing {
+            let initial = todo_list.initial_state().to_string();
+            todo_list.set_status(&id, &initial);
+            summary.reopened += 1;
+        }
+
+        let Some(item) = todo_list.get_todo_by_id_mut(&id) else { continue };
+        let before = item.clone();
+        item.title = comment.text.clone();
+        let tag = comment.kind.to_lowercase();
+        if !item.has_tag(&tag) {
+            item.tags.push(tag);
+        }
+        item.extensions.insert(EXTENSION_KEY.to_string(), comment.extension());
+
+        if linked.is_some() && !was_missing && *item != before {
+            summary.updated += 1;
+        }
+    }
+
+    let vanished: Vec<Uuid> = todo_list.items.iter()
+        .filter(|item| item.extensions.contains_key(EXTENSION_KEY) && !matched.contains(&item.id) && !item.is_completed())
+        .map(|item| item.id)
+        .collect();
+    let final_state = todo_list.final_state().to_string();
+    for id in vanished {
+        todo_list.set_status(&id, &final_state);
+        if let Some(Value::Object(extension)) = todo_list.get_todo_by_id_mut(&id).and_then(|item| item.extensions.get_mut(EXTENSION_KEY)) {
+            extension.insert("missing".to_string(), json!(true));
+        }
+        summary.completed += 1;
+    }
+
+    summary
+}
+
+fn parse_comment(line: &str) -> Option<(&'static str, Option<String>, String)> {
+    for kind in MARKERS {
+        for (position, _) in line.match_indices(kind) {
+            let before = &line[..position];
+            if before.chars().next_back().is_some_and(|c| c.is_alphanumeric() || c == '_') {
+                continue;
+            }
+            let trimmed = before.trim_start();
+            if !COMMENT_LEADERS.iter().any(|leader| before.contains(leader)) && !trimmed.starts_with('*') {
+                continue;
+            }
+
+            let mut rest = &line[position + kind.len()..];
+            let mut author = None;
+            if let Some(inner) = rest.strip_prefix('(') {
+                let Some(end) = inner.find(')') else { continue };
+                author = Some(inner[..end].trim().to_string()).filter(|author| !author.is_empty());
+                rest = &inner[end + 1..];
+            }
+            let Some(text) = rest.strip_prefix(':') else { continue };
+
+            let mut text = text.trim();
+            for closer in COMMENT_CLOSERS {
+                text = text.strip_suffix(closer).unwrap_or(text).trim_end();
+            }
+            if !text.is_empty() {
+                return Some((kind, author, text.to_string()));
+            }
+        }
+    }
+    None
+}
+
+fn scan_field<'a>(item: &'a TodoItem, field: &str) -> Option<&'a str> {
+    item.extensions.get(EXTENSION_KEY)?.get(field)?.as_str()
+}
+
+fn scan_line(item: &TodoItem) -> Option<usize> {
+    item.extensions.get(EXTENSION_KEY)?.get("line")?.as_u64().map(|line| line as usize)
+}
+
+fn scan_missing(item: &TodoItem) -> bool {
+    item.extensions.get(EXTENSION_KEY)
+        .and_then(|extension| extension.get("missing"))
+        .is_some_and(|missing| missing == true)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const SOURCE: &str = "fn main() {
+    // TODO: handle errors
+    let TODO_COUNT: usize = 0;
+    /* FIXME(ana): leaks memory */
+    # HACK: shell style
+    // TODO: handle errors
+    // TODOS: not a marker
+    // Note: TODO without colon
+}
+";
+
+    fn scan(content: &str) -> Vec<CodeComment> {
+        find_comments(Path::new("src/main.rs"), content)
+    }
+
+    #[test]
+    fn finds_marked_comments() {
+        let comments = scan(SOURCE);
+        let found: Vec<(usize, &str, &str)> = comments.iter()
+            .map(|comment| (comment.line, comment.kind, comment.text.as_str()))
+            .collect();
+        assert_eq!(found, vec![
+            (2, "TODO", "handle errors"),
+            (4, "FIXME", "leaks memory"),
+            (5, "HACK", "shell style"),
+            (6, "TODO", "handle errors"),
+        ]);
+        assert_eq!(comments[1].author.as_deref(), Some("ana"));
+        assert_eq!(comments[1].location(), "src/main.rs:4");
+        assert_ne!(comments[0].fingerprint, comments[3].fingerprint);
+    }
+
+    #[test]
+    fn fingerprints_survive_moves() {
+        let before = scan(SOURCE);
+        let after = scan(&format!("\n\n{}", SOURCE));
+        assert_eq!(after[0].line, before[0].line + 2);
+        let fingerprints = |comments: &[CodeComment]| comments.iter().map(|comment| comment.fingerprint.clone()).collect::<Vec<_>>();
+        assert_eq!(fingerprints(&before), fingerprints(&after));
+    }
+
+    #[test]
+    fn sync_adds_updates_and_completes() {
+        let mut todo_list = TodoList::new();
+        let summary = sync(&mut todo_list, &scan(SOURCE));
+        assert_eq!((summary.added, summary.updated, summary.completed), (4, 0, 0));
+        let first = todo_list.items[0].id;
+        assert_eq!(todo_list.items[0].description.as_deref(), Some("Found at `src/main.rs:2`"));
+        assert_eq!(todo_list.items[1].tags, vec!["fixme".to_string()]);
+
+        todo_list.get_todo_by_id_mut(&first).unwrap().description = Some("Wrap in anyhow".to_string());
+        let moved = format!("\n{}", SOURCE.replace("FIXME(ana): leaks memory", "FIXME(ana): leaks a lot of memory"));
+        let summary = sync(&mut todo_list, &scan(&moved));
+        assert_eq!((summary.added, summary.updated, summary.completed), (0, 4, 0));
+        let item = todo_list.get_todo_by_id(&first).unwrap();
+        assert_eq!(item.description.as_deref(), Some("Wrap in anyhow"));
+        assert_eq!(scan_line(item), Some(3));
+        assert_eq!(todo_list.items[1].title, "leaks a lot of memory");
+
+        let without_hack = moved.replace("# HACK: shell style\n", "");
+        let summary = sync(&mut todo_list, &scan(&without_hack));
+        assert_eq!(summary.completed, 1);
+        assert!(todo_list.items[2].is_completed());
+
+        let summary = sync(&mut todo_list, &scan(&moved));
+        assert_eq!((summary.added, summary.reopened), (0, 1));
+        assert!(!todo_list.items[2].is_completed());
+        assert_eq!(todo_list.items.len(), 4);
+    }
+
+    #[test]
+    fn scanned_todos_save_to_toml() {
+        let directory = std::env::temp_dir().join(format!("termtask-scan-{}", Uuid::new_v4()));
+        let store = crate::storage::open_store(directory.join("todos.toml")).unwrap();
+
+        let mut todo_list = TodoList::new();
+        let comments = scan(SOURCE);
+        assert!(comments[0].author.is_none());
+        sync(&mut todo_list, &comments);
+        assert!(todo_list.items[0].extensions[EXTENSION_KEY].get("author").is_none());
+        store.save(&todo_list).unwrap();
+
+        let loaded = store.load().unwrap();
+        assert_eq!(loaded.items.len(), 4);
+        assert_eq!(loaded.items[1].extensions[EXTENSION_KEY]["author"], "ana");
+        let summary = sync(&mut loaded.clone(), &comments);
+        assert_eq!((summary.added, summary.updated), (0, 0));
+
+        std::fs::remove_dir_all(&directory).unwrap();
+    }
+}
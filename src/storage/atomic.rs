@@ -0,0 +1,49 @@
+use anyhow::{Context, Result};
+use std::fs::{self, File};
+use std::io::Write;
+use std::path::Path;
+
+pub fn write_atomic(path: &Path, content: &[u8]) -> Result<()> {
+    let parent = match path.parent() {
+        Some(parent) if !parent.as_os_str().is_empty() => parent,
+        _ => Path::new("."),
+    };
+    fs::create_dir_all(parent)
+        .with_context(|| format!("Failed to create directory: {}", parent.display()))?;
+
+    let file_name = path.file_name().unwrap_or_default().to_string_lossy();
+    let temp_path = parent.join(format!(".{}.tmp-{}", file_name, std::process::id()));
+
+    let result = write_and_sync(&temp_path, content).and_then(|_| {
+        fs::rename(&temp_path, path)
+            .with_context(|| format!("Failed to replace file: {}", path.display()))
+    });
+
+    if result.is_err() {
+        let _ = fs::remove_file(&temp_path);
+        return result;
+    }
+
+    sync_directory(parent);
+    Ok(())
+}
+
+fn write_and_sync(temp_path: &Path, content: &[u8]) -> Result<()> {
+    let mut file = File::create(temp_path)
+        .with_context(|| format!("Failed to create temporary file: {}", temp_path.display()))?;
+    file.write_all(content)
+        .with_context(|| format!("Failed to write temporary file: {}", temp_path.display()))?;
+    file.sync_all()
+        .with_context(|| format!("Failed to flush temporary file: {}", temp_path.display()))?;
+    Ok(())
+}
+
+#[cfg(unix)]
+fn sync_directory(dir: &Path) {
+    if let Ok(dir) = File::open(dir) {
+        let _ = dir.sync_all();
+    }
+}
+
+#[cfg(not(unix))]
+fn sync_directory(_dir: &Path) {}
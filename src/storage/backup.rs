@@ -0,0 +1,202 @@
+use crate::models::{fnv1a, TodoList};
+use crate::storage::open_store;
+use crate::storage::paths::data_dir;
+use anyhow::{Context, Result};
+use chrono::{DateTime, Duration, NaiveDateTime, Utc};
+use std::fs;
+use std::path::{Path, PathBuf};
+
+pub const BACKUP_LIMIT: usize = 10;
+const BACKUP_INTERVAL_MINUTES: i64 = 15;
+const TIMESTAMP_FORMAT: &str = "%Y%m%dT%H%M%S%.3fZ";
+
+pub fn storage_key(file_path: &Path) -> String {
+    let absolute = canonical_path(file_path);
+    let absolute = absolute.to_string_lossy();
+    let key: String = absolute
+        .chars()
+        .map(|c| if c.is_ascii_alphanumeric() || c == '.' || c == '-' { c } else { '_' })
+        .collect();
+    // The hash keeps paths apart that only differ in characters replaced above.
+    format!("{}-{:016x}", key.trim_matches('_'), fnv1a(&[&absolute]))
+}
+
+/// The canonical form of `file_path`, also before the file exists: the parent directory is
+/// resolved instead, so the key doesn't change once the file is created.
+fn canonical_path(file_path: &Path) -> PathBuf {
+    if let Ok(canonical) = file_path.canonicalize() {
+        return canonical;
+    }
+
+    let absolute = std::path::absolute(file_path).unwrap_or_else(|_| file_path.to_path_buf());
+    match (absolute.parent(), absolute.file_name()) {
+        (Some(parent), Some(name)) => parent.canonicalize().map_or(absolute.clone(), |parent| parent.join(name)),
+        _ => absolute,
+    }
+}
+
+pub struct BackupInfo {
+    pub path: PathBuf,
+    pub created_at: DateTime<Utc>,
+    /// Set for the copy kept by `keep_original` before a file is upgraded from this schema version.
+    pub original_version: Option<u32>,
+}
+
+impl BackupInfo {
+    /// Reads the backup through a scratch copy, since opening a store (a database in
+    /// particular) may write to the file it opens.
+    pub fn load(&self) -> Result<TodoList> {
+        let file_name = self.path.file_name().context("Backup has no file name")?;
+        let scratch = std::env::temp_dir().join(format!(
+            "termtask-backup-{}-{}",
+            std::process::id(),
+            file_name.to_string_lossy()
+        ));
+        fs::copy(&self.path, &scratch)
+            .with_context(|| format!("Failed to read backup: {}", self.path.display()))?;
+
+        let todos = open_store(scratch.clone()).and_then(|store| store.load());
+        for suffix in ["", "-journal", "-wal", "-shm"] {
+            let mut path = scratch.clone().into_os_string();
+            path.push(suffix);
+            let _ = fs::remove_file(path);
+        }
+        todos.with_context(|| format!("Failed to read backup: {}", self.path.display()))
+    }
+}
+
+pub struct BackupStore {
+    backup_dir: PathBuf,
+}
+
+impl BackupStore {
+    pub fn for_file(file_path: &Path) -> Result<Self> {
+        let mut backup_dir = data_dir()?;
+        backup_dir.push("backups");
+        backup_dir.push(storage_key(file_path));
+        Ok(Self { backup_dir })
+    }
+
+    pub fn create(&self, source: &Path) -> Result<Option<PathBuf>> {
+        if !source.exists() {
+            return Ok(None);
+        }
+
+        fs::create_dir_all(&self.backup_dir)
+            .with_context(|| format!("Failed to create directory: {}", self.backup_dir.display()))?;
+
+        let extension = source.extension().and_then(|extension| extension.to_str()).unwrap_or("json");
+        let backup_path = self.backup_dir.join(format!("{}.{}", Utc::now().format(TIMESTAMP_FORMAT), extension));
+        fs::copy(source, &backup_path)
+            .with_context(|| format!("Failed to back up todo file to: {}", backup_path.display()))?;
+
+        self.prune()?;
+        Ok(Some(backup_path))
+    }
+
+    pub fn keep_original(&self, source: &Path, version: u32) -> Result<Option<PathBuf>> {
+        if !source.exists() {
+            return Ok(None);
+        }
+
+        fs::create_dir_all(&self.backup_dir)
+            .with_context(|| format!("Failed to create directory: {}", self.backup_dir.display()))?;
+
+        let extension = source.extension().and_then(|extension| extension.to_str()).unwrap_or("json");
+        let backup_path = self.backup_dir.join(format!("v{}.{}", version, extension));
+        if !backup_path.exists() {
+            fs::copy(source, &backup_path)
+                .with_context(|| format!("Failed to back up todo file to: {}", backup_path.display()))?;
+        }
+        Ok(Some(backup_path))
+    }
+
+    pub fn list(&self) -> Result<Vec<BackupInfo>> {
+        if !self.backup_dir.exists() {
+            return Ok(Vec::new());
+        }
+
+        let entries = fs::read_dir(&self.backup_dir)
+            .with_context(|| format!("Failed to read backup directory: {}", self.backup_dir.display()))?;
+
+        let mut backups: Vec<BackupInfo> = entries
+            .filter_map(|entry| entry.ok())
+            .filter_map(|entry| {
+                let path = entry.path();
+                let stem = path.file_stem()?.to_str()?;
+                if let Some(version) = stem.strip_prefix('v').and_then(|version| version.parse().ok()) {
+                    let created_at = entry.metadata().ok()?.modified().ok()?.into();
+                    return Some(BackupInfo { path, created_at, original_version: Some(version) });
+                }
+                let created_at = NaiveDateTime::parse_from_str(stem, TIMESTAMP_FORMAT).ok()?.and_utc();
+                Some(BackupInfo { path, created_at, original_version: None })
+            })
+            .collect();
+
+        backups.sort_by_key(|backup| std::cmp::Reverse(backup.created_at));
+        Ok(backups)
+    }
+
+    pub fn is_due(&self) -> Result<bool> {
+        let latest = self.list()?.into_iter().find(|backup| backup.original_version.is_none());
+        Ok(latest.is_none_or(|backup| Utc::now() - backup.created_at >= Duration::minutes(BACKUP_INTERVAL_MINUTES)))
+    }
+
+    fn prune(&self) -> Result<()> {
+        let backups = self.list()?.into_iter().filter(|backup| backup.original_version.is_none());
+        for backup in backups.skip(BACKUP_LIMIT) {
+            fs::remove_file(&backup.path)
+                .with_context(|| format!("Failed to remove old backup: {}", backup.path.display()))?;
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn similar_paths_get_different_keys() {
+        let spaced = storage_key(Path::new("/home/me/my project/todo.json"));
+        let underscored = storage_key(Path::new("/home/me/my_project/todo.json"));
+        assert_ne!(spaced, underscored);
+        assert!(spaced.starts_with("home_me_my_project_todo.json-"), "{}", spaced);
+    }
+
+    #[test]
+    fn keys_stay_the_same_once_the_file_exists() {
+        let directory = std::env::temp_dir().join(format!("termtask-key-{}", uuid::Uuid::new_v4()));
+        fs::create_dir_all(&directory).unwrap();
+        let file = directory.join(".").join("todo.json");
+
+        let before = storage_key(&file);
+        fs::write(&file, "[]").unwrap();
+        assert_eq!(storage_key(&file), before);
+        assert_eq!(storage_key(&directory.join("todo.json")), before);
+
+        fs::remove_dir_all(&directory).unwrap();
+    }
+
+    #[test]
+    fn lists_the_pre_upgrade_copy() {
+        let directory = std::env::temp_dir().join(format!("termtask-backups-{}", uuid::Uuid::new_v4()));
+        fs::create_dir_all(&directory).unwrap();
+        let source = directory.join("todo.json");
+        fs::write(&source, "[]").unwrap();
+        let backups = BackupStore { backup_dir: directory.join("backups") };
+
+        backups.keep_original(&source, 1).unwrap();
+        for _ in 0..=BACKUP_LIMIT {
+            backups.create(&source).unwrap();
+            std::thread::sleep(std::time::Duration::from_millis(2));
+        }
+
+        let listed = backups.list().unwrap();
+        assert_eq!(listed.len(), BACKUP_LIMIT + 1);
+        assert_eq!(listed.iter().filter(|backup| backup.original_version == Some(1)).count(), 1);
+        assert!(!backups.is_due().unwrap());
+
+        fs::remove_dir_all(&directory).unwrap();
+    }
+}
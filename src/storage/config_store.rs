@@ -1,4 +1,5 @@
 use crate::models::AppConfig;
+use crate::storage::atomic::write_atomic;
 use anyhow::{Context, Result};
 use std::fs;
 use std::path::PathBuf;
@@ -31,15 +32,10 @@ impl ConfigStore {
     }
 
     pub fn save(&self, config: &AppConfig) -> Result<()> {
-        if let Some(parent) = self.file_path.parent() {
-            fs::create_dir_all(parent)
-                .with_context(|| format!("Failed to create directory: {}", parent.display()))?;
-        }
-
         let content = toml::to_string_pretty(config)
             .context("Failed to serialize config")?;
 
-        fs::write(&self.file_path, content)
+        write_atomic(&self.file_path, content.as_bytes())
             .with_context(|| format!("Failed to write config file: {}", self.file_path.display()))?;
 
         Ok(())
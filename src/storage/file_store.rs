@@ -0,0 +1,169 @@
+use crate::models::{merge_lists, TodoList};
+use crate::storage::atomic::write_atomic;
+use crate::storage::backup::BackupStore;
+use crate::storage::lock::FileLock;
+use crate::storage::migration::migrate;
+use crate::storage::store::TodoStore;
+use anyhow::{Context, Result};
+use serde_json::Value;
+use std::cell::{Cell, RefCell};
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::time::SystemTime;
+
+pub enum SaveOutcome {
+    Written,
+    Merged { list: TodoList, conflicts: usize },
+}
+
+pub trait DocumentFormat {
+    fn parse(&self, content: &str) -> Result<Value>;
+    fn serialize(&self, todo_list: &TodoList) -> Result<String>;
+}
+
+struct DiskSnapshot {
+    modified: Option<SystemTime>,
+    content: String,
+}
+
+pub struct FileStore<F: DocumentFormat> {
+    file_path: PathBuf,
+    format: F,
+    upgraded_from: Cell<Option<u32>>,
+    synced: RefCell<Option<DiskSnapshot>>,
+    checked_modified: Cell<Option<SystemTime>>,
+}
+
+impl<F: DocumentFormat + Default> FileStore<F> {
+    pub fn new(file_path: PathBuf) -> Self {
+        Self {
+            file_path,
+            format: F::default(),
+            upgraded_from: Cell::new(None),
+            synced: RefCell::new(None),
+            checked_modified: Cell::new(None),
+        }
+    }
+}
+
+impl<F: DocumentFormat> TodoStore for FileStore<F> {
+    fn load(&self) -> Result<TodoList> {
+        let snapshot = self.read_snapshot()?;
+        let todo_list = match &snapshot {
+            Some(snapshot) => {
+                let (todo_list, upgraded_from) = self.parse(&snapshot.content)?;
+                self.upgraded_from.set(upgraded_from);
+                todo_list
+            }
+            None => TodoList::new(),
+        };
+
+        self.checked_modified.set(snapshot.as_ref().and_then(|snapshot| snapshot.modified));
+        self.synced.replace(snapshot);
+        Ok(todo_list)
+    }
+
+    fn changed_externally(&self) -> Result<bool> {
+        let modified = fs::metadata(&self.file_path).and_then(|metadata| metadata.modified()).ok();
+        if modified == self.checked_modified.get() {
+            return Ok(false);
+        }
+        self.checked_modified.set(modified);
+
+        let disk = self.read_snapshot()?;
+        let synced = self.synced.borrow();
+        Ok(match (&*synced, &disk) {
+            (Some(synced), Some(disk)) => synced.content != disk.content,
+            (None, None) => false,
+            _ => true,
+        })
+    }
+
+    fn save(&self, todo_list: &TodoList) -> Result<SaveOutcome> {
+        let _lock = FileLock::acquire(&self.file_path)?;
+
+        let disk = self.read_snapshot()?;
+        let merged = match &disk {
+            Some(disk) if self.changed_on_disk(disk) => {
+                let base = match &*self.synced.borrow() {
+                    Some(synced) => self.parse(&synced.content)?.0,
+                    None => TodoList::new(),
+                };
+                let theirs = self.parse(&disk.content)?.0;
+                Some(merge_lists(&base, todo_list, &theirs))
+            }
+            _ => None,
+        };
+
+        let to_write = merged.as_ref().map_or(todo_list, |merged| &merged.list);
+        let content = self.format.serialize(to_write)
+            .context("Failed to serialize todo list")?;
+
+        let backups = BackupStore::for_file(&self.file_path)?;
+        if let Some(version) = self.upgraded_from.take() {
+            backups.keep_original(&self.file_path, version)?;
+        }
+
+        if self.file_path.exists() && backups.is_due()? {
+            backups.create(&self.file_path)?;
+        }
+
+        write_atomic(&self.file_path, content.as_bytes())
+            .with_context(|| format!("Failed to write todo file: {}", self.file_path.display()))?;
+
+        let modified = fs::metadata(&self.file_path).and_then(|metadata| metadata.modified()).ok();
+        self.checked_modified.set(modified);
+        self.synced.replace(Some(DiskSnapshot { modified, content }));
+
+        Ok(match merged {
+            Some(merged) => SaveOutcome::Merged { list: merged.list, conflicts: merged.conflicts },
+            None => SaveOutcome::Written,
+        })
+    }
+
+    fn get_file_path(&self) -> &Path {
+        &self.file_path
+    }
+}
+
+impl<F: DocumentFormat> FileStore<F> {
+    fn read_snapshot(&self) -> Result<Option<DiskSnapshot>> {
+        if !self.file_path.exists() {
+            return Ok(None);
+        }
+
+        let modified = fs::metadata(&self.file_path).and_then(|metadata| metadata.modified()).ok();
+        let content = fs::read_to_string(&self.file_path)
+            .with_context(|| format!("Failed to read todo file: {}", self.file_path.display()))?;
+
+        if content.trim().is_empty() {
+            return Ok(None);
+        }
+
+        Ok(Some(DiskSnapshot { modified, content }))
+    }
+
+    fn changed_on_disk(&self, disk: &DiskSnapshot) -> bool {
+        match &*self.synced.borrow() {
+            Some(synced) => {
+                (synced.modified.is_none() || synced.modified != disk.modified) && synced.content != disk.content
+            }
+            None => true,
+        }
+    }
+
+    fn parse(&self, content: &str) -> Result<(TodoList, Option<u32>)> {
+        let mut value = self.format.parse(content)
+            .with_context(|| format!("Failed to parse todo file: {}", self.file_path.display()))?;
+        let upgraded_from = migrate(&mut value)
+            .with_context(|| format!("Cannot open todo file: {}", self.file_path.display()))?;
+
+        let mut todo_list: TodoList = serde_json::from_value(value)
+            .with_context(|| format!("Failed to parse todo file: {}", self.file_path.display()))?;
+
+        todo_list.normalize_statuses();
+        todo_list.normalize_order();
+
+        Ok((todo_list, upgraded_from))
+    }
+}
@@ -0,0 +1,31 @@
+use crate::storage::backup::storage_key;
+use crate::storage::paths::data_dir;
+use anyhow::{Context, Result};
+use fs4::fs_std::FileExt;
+use std::fs::{self, File};
+use std::path::Path;
+
+pub struct FileLock {
+    _file: File,
+}
+
+impl FileLock {
+    pub fn acquire(file_path: &Path) -> Result<Self> {
+        let mut lock_path = data_dir()?;
+        lock_path.push("locks");
+        fs::create_dir_all(&lock_path)
+            .with_context(|| format!("Failed to create directory: {}", lock_path.display()))?;
+        lock_path.push(format!("{}.lock", storage_key(file_path)));
+
+        let file = File::options()
+            .create(true)
+            .truncate(false)
+            .write(true)
+            .open(&lock_path)
+            .with_context(|| format!("Failed to open lock file: {}", lock_path.display()))?;
+        FileExt::lock_exclusive(&file)
+            .with_context(|| format!("Failed to lock todo file: {}", file_path.display()))?;
+
+        Ok(Self { _file: file })
+    }
+}
@@ -0,0 +1,491 @@
+use crate::models::{default_workflow, fnv1a, TodoItem, TodoList, CURRENT_VERSION};
+use crate::storage::file_store::{DocumentFormat, FileStore};
+use anyhow::Result;
+use chrono::{DateTime, Utc};
+use serde_json::{json, Map, Value};
+use std::cell::RefCell;
+use std::collections::HashMap;
+use uuid::Uuid;
+
+const DEFAULT_TITLE: &str = "TODO";
+const METADATA_START: &str = "<!--";
+const METADATA_END: &str = "-->";
+const WORKFLOW_PREFIX: &str = "termtask workflow:";
+const MARKDOWN_FIELDS: [&str; 6] = ["title", "description", "tags", "parent_id", "order", "completed_at"];
+
+#[derive(Default)]
+pub struct MarkdownFormat {
+    title: RefCell<Option<String>>,
+    sections: RefCell<Vec<String>>,
+    placements: RefCell<HashMap<Uuid, String>>,
+    inferred_created: RefCell<HashMap<Uuid, DateTime<Utc>>>,
+    inferred_completed: RefCell<HashMap<Uuid, DateTime<Utc>>>,
+}
+
+pub type MarkdownStore = FileStore<MarkdownFormat>;
+
+struct ParsedItem {
+    indent: usize,
+    fields: Map<String, Value>,
+    description: Vec<String>,
+    blank_lines: usize,
+}
+
+impl DocumentFormat for MarkdownFormat {
+    fn parse(&self, content: &str) -> Result<Value> {
+        let mut title = None;
+        let mut workflow = default_workflow();
+        let mut heading_tag: Option<String> = None;
+        let mut sections: Vec<String> = Vec::new();
+        let mut placements: HashMap<Uuid, String> = HashMap::new();
+        let mut items: Vec<ParsedItem> = Vec::new();
+        let mut parents: Vec<(usize, usize)> = Vec::new();
+        let mut seen_titles: HashMap<String, usize> = HashMap::new();
+        let mut inferred_created = self.inferred_created.borrow_mut();
+        let mut inferred_completed = self.inferred_completed.borrow_mut();
+        let mut in_item = false;
+
+        for line in content.lines() {
+            let line = line.trim_end();
+            let indent = line.len() - line.trim_start().len();
+            let trimmed = line.trim_start();
+
+            if trimmed.is_empty() {
+                if let Some(item) = items.last_mut() {
+                    item.blank_lines += 1;
+                }
+                continue;
+            }
+
+            if let Some(states) = comment_body(trimmed).and_then(|body| body.strip_prefix(WORKFLOW_PREFIX)) {
+                workflow = states.split(',').map(|state| state.trim().to_string()).filter(|state| !state.is_empty()).collect();
+                continue;
+            }
+
+            if indent == 0 && trimmed.starts_with('#') {
+                let level = trimmed.chars().take_while(|c| *c == '#').count();
+                let text = trimmed[level..].trim().to_string();
+                if level == 1 && title.is_none() && items.is_empty() {
+                    title = Some(text);
+                } else {
+                    if !text.is_empty() && !sections.contains(&text) {
+                        sections.push(text.clone());
+                    }
+                    heading_tag = (!text.is_empty()).then_some(text);
+                }
+                parents.clear();
+                in_item = false;
+                continue;
+            }
+
+            if let Some((checked, text)) = parse_checkbox(trimmed) {
+                while parents.last().is_some_and(|(parent_indent, _)| *parent_indent >= indent) {
+                    parents.pop();
+                }
+                let parent = parents.last().map(|(_, index)| *index);
+
+                let (text, metadata) = split_metadata(text);
+                let (title, mut tags) = split_tags(text);
+
+                let mut fields = metadata;
+                let occurrence = seen_titles.entry(title.clone()).or_insert(0);
+                *occurrence += 1;
+                fields.entry("id").or_insert_with(|| json!(stable_id(&title, *occurrence)));
+                let id = fields["id"].as_str().and_then(|id| Uuid::parse_str(id).ok()).unwrap_or_else(Uuid::nil);
+
+                if parent.is_none() {
+                    if let Some(tag) = &heading_tag {
+                        if !tags.iter().any(|existing| existing.eq_ignore_ascii_case(tag)) {
+                            tags.insert(0, tag.clone());
+                        }
+                        placements.insert(id, tag.clone());
+                    }
+                }
+                fields.entry("created_at").or_insert_with(|| json!(inferred_created.entry(id).or_insert_with(Utc::now)));
+
+                if checked {
+                    fields.insert("status".to_string(), json!(workflow.last()));
+                    fields.entry("completed_at").or_insert_with(|| json!(inferred_completed.entry(id).or_insert_with(Utc::now)));
+                } else {
+                    let status = fields.get("status").and_then(Value::as_str);
+                    if status.is_none_or(|status| Some(status) == workflow.last().map(String::as_str)) {
+                        fields.insert("status".to_string(), json!(workflow.first()));
+                    }
+                    fields.insert("completed_at".to_string(), Value::Null);
+                }
+
+                fields.insert("title".to_string(), json!(title));
+                fields.insert("tags".to_string(), json!(tags));
+                fields.insert("order".to_string(), json!(items.len()));
+                if let Some(parent) = parent {
+                    fields.insert("parent_id".to_string(), items[parent].fields["id"].clone());
+                }
+
+                parents.push((indent, items.len()));
+                items.push(ParsedItem { indent, fields, description: Vec::new(), blank_lines: 0 });
+                in_item = true;
+                continue;
+            }
+
+            match items.last_mut().filter(|_| in_item) {
+                Some(item) if indent > item.indent => {
+                    let description_indent = (item.indent + 2).min(indent);
+                    if !item.description.is_empty() {
+                        item.description.extend(std::iter::repeat_n(String::new(), item.blank_lines));
+                    }
+                    item.blank_lines = 0;
+                    item.description.push(unescape_description_line(&line[description_indent..]));
+                }
+                _ => {
+                    parents.clear();
+                    in_item = false;
+                }
+            }
+        }
+
+        self.title.replace(title);
+        self.sections.replace(sections);
+        self.placements.replace(placements);
+
+        let items: Vec<Value> = items.into_iter()
+            .map(|mut item| {
+                let description = item.description.join("\n");
+                item.fields.insert("description".to_string(), if description.is_empty() { Value::Null } else { json!(description) });
+                Value::Object(item.fields)
+            })
+            .collect();
+
+        Ok(json!({
+            "version": CURRENT_VERSION,
+            "workflow": workflow,
+            "items": items,
+        }))
+    }
+
+    fn serialize(&self, todo_list: &TodoList) -> Result<String> {
+        let mut output = format!("# {}\n", self.title.borrow().as_deref().unwrap_or(DEFAULT_TITLE));
+
+        if todo_list.workflow != default_workflow() {
+            output.push_str(&format!("\n{} {} {} {}\n", METADATA_START, WORKFLOW_PREFIX, todo_list.workflow.join(", "), METADATA_END));
+        }
+
+        let mut roots: Vec<&TodoItem> = todo_list.items.iter()
+            .filter(|item| item.parent_id.is_none_or(|parent| todo_list.get_todo_by_id(&parent).is_none()))
+            .collect();
+        roots.sort_by_key(|item| item.order);
+
+        let sections = self.sections.borrow().clone();
+        let mut placements = HashMap::new();
+        for item in &roots {
+            let previous = self.placements.borrow().get(&item.id).cloned();
+            let section = previous
+                .filter(|section| item.has_tag(section))
+                .or_else(|| item.tags.first().and_then(|tag| {
+                    sections.iter().find(|section| section.eq_ignore_ascii_case(tag)).cloned()
+                }));
+            if let Some(section) = section {
+                placements.insert(item.id, section);
+            }
+        }
+
+        let mut writer = ItemWriter { format: self, todo_list, seen_titles: HashMap::new() };
+        let unplaced: Vec<&TodoItem> = roots.iter().copied().filter(|item| !placements.contains_key(&item.id)).collect();
+        if !unplaced.is_empty() {
+            output.push('\n');
+            for item in unplaced {
+                writer.write(&mut output, item, 0, None)?;
+            }
+        }
+
+        for section in &sections {
+            let items: Vec<&TodoItem> = roots.iter().copied()
+                .filter(|item| placements.get(&item.id) == Some(section))
+                .collect();
+            if items.is_empty() {
+                continue;
+            }
+
+            output.push_str(&format!("\n## {}\n\n", section));
+            for item in items {
+                writer.write(&mut output, item, 0, Some(section))?;
+            }
+        }
+
+        self.placements.replace(placements);
+        Ok(output)
+    }
+}
+
+struct ItemWriter<'a> {
+    format: &'a MarkdownFormat,
+    todo_list: &'a TodoList,
+    seen_titles: HashMap<String, usize>,
+}
+
+impl ItemWriter<'_> {
+    fn write(&mut self, output: &mut String, item: &TodoItem, depth: usize, section: Option<&str>) -> Result<()> {
+        let indent = "  ".repeat(depth);
+        let checkbox = if item.is_completed() { "x" } else { " " };
+        let inline_tags = item.tags.iter().filter(|tag| section.is_none_or(|section| !tag.eq_ignore_ascii_case(section)));
+
+        let title = item.title.trim();
+        output.push_str(&format!("{}- [{}] {}", indent, checkbox, escape_title(title)));
+        for tag in inline_tags {
+            output.push_str(&format!(" #{}", tag.replace(' ', "-")));
+        }
+
+        let occurrence = self.seen_titles.entry(title.to_string()).or_insert(0);
+        *occurrence += 1;
+        let inferred_id = stable_id(title, *occurrence);
+        if let Some(metadata) = self.metadata_json(item, inferred_id)? {
+            output.push_str(&format!(" {} {} {}", METADATA_START, metadata, METADATA_END));
+        }
+        output.push('\n');
+
+        if let Some(description) = item.description.as_deref().filter(|description| !description.trim().is_empty()) {
+            for line in description.trim_end().lines() {
+                if line.trim().is_empty() {
+                    output.push('\n');
+                } else {
+                    output.push_str(&format!("{}  {}\n", indent, escape_description_line(line)));
+                }
+            }
+        }
+
+        let mut children = self.todo_list.get_children(&item.id);
+        children.sort_by_key(|child| child.order);
+        for child in children {
+            self.write(output, child, depth + 1, None)?;
+        }
+        Ok(())
+    }
+
+    fn metadata_json(&self, item: &TodoItem, inferred_id: Uuid) -> Result<Option<String>> {
+        let mut value = serde_json::to_value(item)?;
+        let Some(fields) = value.as_object_mut() else { return Ok(None) };
+
+        for field in MARKDOWN_FIELDS {
+            fields.remove(field);
+        }
+        if item.id == inferred_id {
+            fields.remove("id");
+        }
+        if self.format.inferred_created.borrow().get(&item.id) == Some(&item.created_at) {
+            fields.remove("created_at");
+        }
+        if let Some(completed_at) = item.completed_at {
+            if self.format.inferred_completed.borrow().get(&item.id) != Some(&completed_at) {
+                fields.insert("completed_at".to_string(), json!(completed_at));
+            }
+        }
+        if item.status == self.todo_list.initial_state() || item.status == self.todo_list.final_state() {
+            fields.remove("status");
+        }
+
+        if fields.is_empty() {
+            return Ok(None);
+        }
+        Ok(Some(serde_json::to_string(&value)?.replace("--", "\\u002d\\u002d")))
+    }
+}
+
+fn parse_checkbox(line: &str) -> Option<(bool, &str)> {
+    let rest = line.strip_prefix("- [").or_else(|| line.strip_prefix("* ["))?;
+    let mut chars = rest.chars();
+    let mark = chars.next()?;
+    let rest = chars.as_str().strip_prefix(']')?;
+    let checked = match mark {
+        ' ' => false,
+        'x' | 'X' => true,
+        _ => return None,
+    };
+    Some((checked, rest.trim()))
+}
+
+fn comment_body(text: &str) -> Option<&str> {
+    text.strip_prefix(METADATA_START)?.strip_suffix(METADATA_END).map(str::trim)
+}
+
+fn split_metadata(text: &str) -> (&str, Map<String, Value>) {
+    if let Some(start) = text.rfind(METADATA_START) {
+        if let Some(Ok(Value::Object(fields))) = comment_body(&text[start..]).map(serde_json::from_str::<Value>) {
+            return (text[..start].trim_end(), fields);
+        }
+    }
+    (text, Map::new())
+}
+
+fn split_tags(text: &str) -> (String, Vec<String>) {
+    let mut title = text.trim_end();
+    let mut tags = Vec::new();
+
+    while let Some(start) = last_word_start(title).filter(|start| *start > 0) {
+        let Some(tag) = title[start..].strip_prefix('#').filter(|tag| is_tag(tag)) else { break };
+        tags.insert(0, tag.to_string());
+        title = title[..start].trim_end();
+    }
+
+    (unescape_title(title), tags)
+}
+
+fn last_word_start(text: &str) -> Option<usize> {
+    if text.is_empty() {
+        return None;
+    }
+    Some(text.char_indices().rev().find(|(_, c)| c.is_whitespace()).map_or(0, |(index, c)| index + c.len_utf8()))
+}
+
+fn is_tag(tag: &str) -> bool {
+    tag.starts_with(|c: char| c.is_alphabetic() || c == '@')
+}
+
+pub(crate) fn escape_title(title: &str) -> String {
+    let mut output = title.to_string();
+    let mut rest = title;
+    while let Some(start) = last_word_start(rest).filter(|start| *start > 0) {
+        if !rest[start..].strip_prefix('#').is_some_and(is_tag) {
+            break;
+        }
+        output.insert(start, '\\');
+        rest = rest[..start].trim_end();
+    }
+    output
+}
+
+fn unescape_title(title: &str) -> String {
+    let mut output = String::with_capacity(title.len());
+    let mut previous = None;
+    let mut chars = title.chars().peekable();
+    while let Some(c) = chars.next() {
+        let at_word_start = previous.is_none_or(char::is_whitespace);
+        if c == '\\' && at_word_start && chars.peek() == Some(&'#') {
+            previous = Some(c);
+            continue;
+        }
+        output.push(c);
+        previous = Some(c);
+    }
+    output
+}
+
+fn escape_description_line(line: &str) -> String {
+    let content = line.trim_start();
+    if parse_checkbox(content).is_some() {
+        format!("{}\\{}", &line[..line.len() - content.len()], content)
+    } else {
+        line.to_string()
+    }
+}
+
+fn unescape_description_line(line: &str) -> String {
+    let content = line.trim_start();
+    match content.strip_prefix('\\').filter(|rest| parse_checkbox(rest).is_some()) {
+        Some(rest) => format!("{}{}", &line[..line.len() - content.len()], rest),
+        None => line.to_string(),
+    }
+}
+
+fn stable_id(title: &str, occurrence: usize) -> Uuid {
+    let occurrence = occurrence.to_string();
+    Uuid::from_u64_pair(fnv1a(&["0", title, &occurrence]), fnv1a(&["1", title, &occurrence]))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::models::Priority;
+    use chrono::{Duration, TimeZone};
+
+    fn load(format: &MarkdownFormat, content: &str) -> TodoList {
+        let mut todos: TodoList = serde_json::from_value(format.parse(content).unwrap()).unwrap();
+        todos.normalize_statuses();
+        todos.normalize_order();
+        todos
+    }
+
+    const HAND_WRITTEN: &str = "# Groceries
+
+- [ ] Buy milk #dairy
+- [x] Bake  bread
+  Use the old recipe.
+
+  Let it rest overnight.
+  - [ ] Buy flour
+
+## Later
+
+- [ ] Clean the fridge
+";
+
+    #[test]
+    fn hand_written_files_are_saved_unchanged() {
+        let format = MarkdownFormat::default();
+        let todos = load(&format, HAND_WRITTEN);
+
+        assert_eq!(todos.items.len(), 4);
+        assert_eq!(todos.items[1].title, "Bake  bread");
+        assert_eq!(todos.items[1].description.as_deref(), Some("Use the old recipe.\n\nLet it rest overnight."));
+        assert_eq!(todos.items[2].parent_id, Some(todos.items[1].id));
+        assert_eq!(todos.items[3].tags, vec!["Later".to_string()]);
+
+        assert_eq!(format.serialize(&todos).unwrap(), HAND_WRITTEN);
+    }
+
+    #[test]
+    fn parsing_is_stable() {
+        let format = MarkdownFormat::default();
+        let first = load(&format, HAND_WRITTEN);
+        let second = load(&format, HAND_WRITTEN);
+        assert_eq!(first, second);
+
+        let other = load(&MarkdownFormat::default(), HAND_WRITTEN);
+        let ids = |todos: &TodoList| todos.items.iter().map(|item| item.id).collect::<Vec<_>>();
+        assert_eq!(ids(&first), ids(&other));
+    }
+
+    #[test]
+    fn only_changed_todos_get_metadata() {
+        let format = MarkdownFormat::default();
+        let mut todos = load(&format, HAND_WRITTEN);
+        let id = todos.add_todo("Buy eggs".to_string(), None);
+        todos.set_priority(&id, Priority::High);
+
+        let output = format.serialize(&todos).unwrap();
+        let commented: Vec<&str> = output.lines().filter(|line| line.contains(METADATA_START)).collect();
+        assert_eq!(commented.len(), 1);
+        assert!(commented[0].starts_with("- [ ] Buy eggs <!-- "), "{}", commented[0]);
+    }
+
+    #[test]
+    fn round_trips_todos_created_in_termtask() {
+        let mut todos = TodoList::new();
+        let release = todos.add_todo("Release  v2 #backend".to_string(), Some("Steps:\n- [ ] tag the commit\n  - [x] build".to_string()));
+        let item = todos.get_todo_by_id_mut(&release).unwrap();
+        item.tags = vec!["ops".to_string()];
+        item.priority = Priority::Urgent;
+        item.due_at = Some(Utc.with_ymd_and_hms(2024, 5, 1, 9, 30, 0).unwrap());
+        todos.set_status(&release, "In Progress");
+
+        let notes = todos.add_child_todo(&release, "Write notes".to_string(), None);
+        todos.set_status(&notes, "Done");
+        todos.get_todo_by_id_mut(&notes).unwrap().updated_at = Some(Utc::now() + Duration::minutes(5));
+
+        let output = MarkdownFormat::default().serialize(&todos).unwrap();
+        assert!(output.contains("- [ ] Release  v2 \\#backend #ops <!-- "), "{}", output);
+        assert!(output.contains("  \\- [ ] tag the commit\n"), "{}", output);
+
+        let loaded = load(&MarkdownFormat::default(), &output);
+        assert_eq!(loaded.items, todos.items);
+    }
+
+    #[test]
+    fn trailing_words_after_escaped_tags_stay_in_the_title() {
+        let format = MarkdownFormat::default();
+        let todos = load(&format, "- [ ] Issue \\#42 #bug\n- [ ] #solo\n");
+        assert_eq!(todos.items[0].title, "Issue #42");
+        assert_eq!(todos.items[0].tags, vec!["bug".to_string()]);
+        assert_eq!(todos.items[1].title, "#solo");
+        assert!(todos.items[1].tags.is_empty());
+        assert_eq!(format.serialize(&todos).unwrap(), "# TODO\n\n- [ ] Issue #42 #bug\n- [ ] #solo\n");
+    }
+}
@@ -0,0 +1,143 @@
+use crate::models::{CURRENT_VERSION, DEFAULT_WORKFLOW};
+use anyhow::{bail, Result};
+use serde_json::Value;
+
+type MigrationStep = fn(&mut Value) -> Result<()>;
+
+const MIGRATIONS: &[(u32, MigrationStep)] = &[
+    (0, migrate_v0_to_v1),
+    (1, migrate_v1_to_v2),
+];
+
+pub fn document_version(value: &Value) -> Result<u32> {
+    match value.get("version") {
+        None | Some(Value::Null) => Ok(0),
+        Some(version) => match version.as_u64().and_then(|version| u32::try_from(version).ok()) {
+            Some(version) => Ok(version),
+            None => bail!("Invalid schema version: {}", version),
+        },
+    }
+}
+
+pub fn migrate(value: &mut Value) -> Result<Option<u32>> {
+    let original_version = document_version(value)?;
+
+    if original_version > CURRENT_VERSION {
+        bail!(
+            "This todo file was written by a newer version of TermTask (schema v{}, this build supports up to v{}). \
+             Please upgrade TermTask before opening it so no data is lost.",
+            original_version,
+            CURRENT_VERSION
+        );
+    }
+
+    if original_version == CURRENT_VERSION {
+        return Ok(None);
+    }
+
+    for version in original_version..CURRENT_VERSION {
+        let step = MIGRATIONS
+            .iter()
+            .find(|(from, _)| *from == version)
+            .map(|(_, step)| step);
+
+        match step {
+            Some(step) => step(value)?,
+            None => bail!("No migration available from schema v{} to v{}", version, version + 1),
+        }
+
+        if let Some(document) = value.as_object_mut() {
+            document.insert("version".to_string(), Value::from(version + 1));
+        }
+    }
+
+    Ok(Some(original_version))
+}
+
+fn migrate_v0_to_v1(value: &mut Value) -> Result<()> {
+    if !value.is_object() {
+        bail!("Expected a JSON object at the top level");
+    }
+
+    if let Some(document) = value.as_object_mut() {
+        document.entry("items").or_insert_with(|| Value::Array(Vec::new()));
+    }
+    Ok(())
+}
+
+fn migrate_v1_to_v2(value: &mut Value) -> Result<()> {
+    let initial = DEFAULT_WORKFLOW[0];
+    let final_state = DEFAULT_WORKFLOW[DEFAULT_WORKFLOW.len() - 1];
+
+    if let Some(items) = value.get_mut("items").and_then(Value::as_array_mut) {
+        for item in items.iter_mut().filter_map(Value::as_object_mut) {
+            let completed = item.remove("completed").and_then(|completed| completed.as_bool()).unwrap_or(false);
+            let status = if completed { final_state } else { initial };
+            item.entry("status").or_insert_with(|| Value::String(status.to_string()));
+        }
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::models::TodoList;
+    use serde_json::json;
+
+    #[test]
+    fn migrates_v1_completed_flags_to_states() {
+        let mut value = json!({
+            "version": 1,
+            "items": [
+                { "id": "7d5f6a2e-3c1b-4e8a-9f0d-2b6c8e4a1f3d", "title": "Open", "completed": false, "created_at": "2024-03-01T10:00:00Z" },
+                { "id": "1a2b3c4d-5e6f-4a8b-9c0d-1e2f3a4b5c6d", "title": "Done", "completed": true, "created_at": "2024-03-01T10:00:00Z", "completed_at": "2024-03-02T10:00:00Z" }
+            ]
+        });
+
+        assert_eq!(migrate(&mut value).unwrap(), Some(1));
+        assert_eq!(value["version"], json!(CURRENT_VERSION));
+        assert_eq!(value["items"][0]["status"], json!("Todo"));
+        assert_eq!(value["items"][1]["status"], json!("Done"));
+        assert!(value["items"][0].get("completed").is_none());
+
+        let todos: TodoList = serde_json::from_value(value).unwrap();
+        assert!(!todos.items[0].is_completed());
+        assert!(todos.items[1].is_completed());
+    }
+
+    #[test]
+    fn migrates_unversioned_documents() {
+        let mut value = json!({});
+        assert_eq!(migrate(&mut value).unwrap(), Some(0));
+        assert_eq!(value, json!({ "version": CURRENT_VERSION, "items": [] }));
+    }
+
+    #[test]
+    fn keeps_existing_status() {
+        let mut value = json!({ "version": 1, "items": [{ "status": "Review", "completed": false }] });
+        migrate(&mut value).unwrap();
+        assert_eq!(value["items"][0]["status"], json!("Review"));
+    }
+
+    #[test]
+    fn leaves_current_documents_alone() {
+        let mut value = json!({ "version": CURRENT_VERSION, "items": [] });
+        let original = value.clone();
+        assert_eq!(migrate(&mut value).unwrap(), None);
+        assert_eq!(value, original);
+    }
+
+    #[test]
+    fn refuses_newer_versions() {
+        let mut value = json!({ "version": CURRENT_VERSION + 1, "items": [] });
+        let error = migrate(&mut value).unwrap_err().to_string();
+        assert!(error.contains("newer version of TermTask"), "{}", error);
+    }
+
+    #[test]
+    fn rejects_invalid_documents() {
+        assert!(migrate(&mut json!({ "version": "two" })).is_err());
+        assert!(migrate(&mut json!([1, 2, 3])).is_err());
+    }
+}
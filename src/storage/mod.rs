@@ -1,5 +1,19 @@
+pub mod atomic;
+pub mod backup;
 pub mod config_store;
+pub mod file_store;
 pub mod json_store;
+pub mod lock;
+pub mod markdown_store;
+pub mod migration;
+pub mod paths;
+#[cfg(feature = "sqlite")]
+pub mod sqlite_store;
+pub mod store;
+pub mod toml_store;
 
+pub use backup::*;
 pub use config_store::*;
-pub use json_store::*;
\ No newline at end of file
+pub use file_store::*;
+pub use json_store::*;
+pub use store::*;
@@ -0,0 +1,21 @@
+use anyhow::{Context, Result};
+use std::path::PathBuf;
+
+pub const DATA_DIR_VARIABLE: &str = "TERMTASK_DATA_DIR";
+
+/// Where the global todo file, backups and locks live. `TERMTASK_DATA_DIR` overrides it;
+/// tests default to a scratch directory so they never touch the user's data.
+pub fn data_dir() -> Result<PathBuf> {
+    if let Some(dir) = std::env::var_os(DATA_DIR_VARIABLE).filter(|dir| !dir.is_empty()) {
+        return Ok(PathBuf::from(dir));
+    }
+
+    if cfg!(test) {
+        return Ok(std::env::temp_dir().join("termtask-tests"));
+    }
+
+    let mut path = dirs::data_local_dir()
+        .context("Could not determine local data directory")?;
+    path.push("termtask");
+    Ok(path)
+}
@@ -0,0 +1,444 @@
+use crate::models::{default_workflow, merge_lists, TodoItem, TodoList, CURRENT_VERSION};
+use crate::storage::backup::BackupStore;
+use crate::storage::file_store::SaveOutcome;
+use crate::storage::store::{TodoQuery, TodoStore};
+use anyhow::{bail, Context, Result};
+use rusqlite::types::ToSql;
+use rusqlite::{params, Connection, OptionalExtension, TransactionBehavior};
+use std::cell::{Cell, RefCell};
+use std::collections::HashMap;
+use std::fs;
+use std::path::{Path, PathBuf};
+use uuid::Uuid;
+
+const SCHEMA: &str = "
+    CREATE TABLE IF NOT EXISTS meta (
+        key TEXT PRIMARY KEY,
+        value TEXT NOT NULL
+    );
+    CREATE TABLE IF NOT EXISTS todos (
+        id TEXT PRIMARY KEY,
+        parent_id TEXT,
+        position INTEGER NOT NULL,
+        completed INTEGER NOT NULL,
+        changed_at INTEGER NOT NULL,
+        data TEXT NOT NULL
+    );
+    CREATE INDEX IF NOT EXISTS todos_position ON todos (position);
+    CREATE INDEX IF NOT EXISTS todos_completed ON todos (completed, position);
+    CREATE INDEX IF NOT EXISTS todos_changed_at ON todos (changed_at);
+    CREATE TABLE IF NOT EXISTS todo_tags (
+        todo_id TEXT NOT NULL,
+        tag TEXT NOT NULL COLLATE NOCASE,
+        PRIMARY KEY (todo_id, tag)
+    );
+    CREATE INDEX IF NOT EXISTS todo_tags_tag ON todo_tags (tag);
+    CREATE VIRTUAL TABLE IF NOT EXISTS todo_text USING fts5 (
+        id UNINDEXED, title, description, tags,
+        tokenize = 'trigram case_sensitive 1'
+    );
+";
+
+// The trigram index only narrows a search down; `TodoQuery::matches` makes the final call,
+// so both stores find the same todos. Shorter searches than this can't use the index.
+const MIN_INDEXED_SEARCH: usize = 3;
+
+#[derive(Clone)]
+struct SyncedState {
+    workflow: Vec<String>,
+    items: HashMap<Uuid, (u32, String)>,
+}
+
+pub struct SqliteStore {
+    file_path: PathBuf,
+    connection: RefCell<Connection>,
+    synced: RefCell<Option<SyncedState>>,
+    data_version: Cell<i64>,
+}
+
+impl SqliteStore {
+    pub fn open(file_path: PathBuf) -> Result<Self> {
+        if let Some(parent) = file_path.parent().filter(|parent| !parent.as_os_str().is_empty()) {
+            fs::create_dir_all(parent)
+                .with_context(|| format!("Failed to create directory: {}", parent.display()))?;
+        }
+
+        let connection = Connection::open(&file_path)
+            .with_context(|| format!("Failed to open todo database: {}", file_path.display()))?;
+        connection.busy_timeout(std::time::Duration::from_secs(5))?;
+        connection.execute_batch(SCHEMA)
+            .with_context(|| format!("Failed to initialize todo database: {}", file_path.display()))?;
+
+        let version: Option<String> = connection
+            .query_row("SELECT value FROM meta WHERE key = 'version'", [], |row| row.get(0))
+            .optional()?;
+        if let Some(version) = version.and_then(|version| version.parse::<u32>().ok()) {
+            if version > CURRENT_VERSION {
+                bail!(
+                    "Cannot open todo database: {}: This todo database was written by a newer version of TermTask (schema v{}, this build supports up to v{}). \
+                     Please upgrade TermTask before opening it so no data is lost.",
+                    file_path.display(),
+                    version,
+                    CURRENT_VERSION
+                );
+            }
+        }
+
+        let store = Self {
+            file_path,
+            connection: RefCell::new(connection),
+            synced: RefCell::new(None),
+            data_version: Cell::new(-1),
+        };
+        store.fill_search_index()
+            .with_context(|| format!("Failed to initialize todo database: {}", store.file_path.display()))?;
+        Ok(store)
+    }
+
+    fn fill_search_index(&self) -> Result<()> {
+        let mut connection = self.connection.borrow_mut();
+        let count = |table: &str| connection.query_row(&format!("SELECT count(*) FROM {}", table), [], |row| row.get::<_, i64>(0));
+        if count("todo_text")? == count("todos")? {
+            return Ok(());
+        }
+
+        let transaction = connection.transaction_with_behavior(TransactionBehavior::Immediate)?;
+        let items = Self::read_all(&transaction)?.items;
+        transaction.execute("DELETE FROM todo_text", [])?;
+        for item in &items {
+            Self::index_text(&transaction, item)?;
+        }
+        transaction.commit()?;
+        Ok(())
+    }
+
+    fn index_text(connection: &Connection, item: &TodoItem) -> Result<()> {
+        connection.prepare_cached("INSERT INTO todo_text (id, title, description, tags) VALUES (?1, ?2, ?3, ?4)")?
+            .execute(params![
+                item.id.to_string(),
+                item.title.to_lowercase(),
+                item.description.as_deref().unwrap_or("").to_lowercase(),
+                item.tags.join(" ").to_lowercase(),
+            ])?;
+        Ok(())
+    }
+
+    fn read_all(connection: &Connection) -> Result<TodoList> {
+        let workflow: Option<String> = connection
+            .query_row("SELECT value FROM meta WHERE key = 'workflow'", [], |row| row.get(0))
+            .optional()?;
+
+        let mut statement = connection.prepare_cached("SELECT position, data FROM todos ORDER BY position")?;
+        let items = statement
+            .query_map([], Self::read_row)?
+            .map(|row| row?)
+            .collect::<Result<Vec<_>>>()?;
+
+        let mut todo_list = TodoList::new();
+        todo_list.workflow = match workflow {
+            Some(workflow) => serde_json::from_str(&workflow)?,
+            None => default_workflow(),
+        };
+        todo_list.items = items;
+        todo_list.normalize_statuses();
+        todo_list.normalize_order();
+        Ok(todo_list)
+    }
+
+    fn read_row(row: &rusqlite::Row) -> rusqlite::Result<Result<TodoItem>> {
+        let (order, data) = (row.get(0)?, row.get::<_, String>(1)?);
+        Ok(serde_json::from_str(&data).map(|item| TodoItem { order, ..item }).map_err(Into::into))
+    }
+
+    fn data_version(connection: &Connection) -> Result<i64> {
+        Ok(connection.query_row("PRAGMA data_version", [], |row| row.get(0))?)
+    }
+
+    fn snapshot(todo_list: &TodoList) -> Result<SyncedState> {
+        let items = todo_list.items.iter()
+            .map(|item| Ok((item.id, (item.order, serde_json::to_string(item)?))))
+            .collect::<Result<HashMap<_, _>>>()?;
+        Ok(SyncedState { workflow: todo_list.workflow.clone(), items })
+    }
+
+    fn synced_list(&self) -> Result<TodoList> {
+        let mut todo_list = TodoList::new();
+        if let Some(synced) = &*self.synced.borrow() {
+            todo_list.workflow = synced.workflow.clone();
+            todo_list.items = synced.items.values()
+                .map(|(order, data)| Ok(TodoItem { order: *order, ..serde_json::from_str(data)? }))
+                .collect::<Result<Vec<_>>>()?;
+            todo_list.normalize_order();
+        }
+        Ok(todo_list)
+    }
+
+    fn write_changes(connection: &Connection, current: &SyncedState, target: &SyncedState, todo_list: &TodoList) -> Result<()> {
+        if current.workflow != target.workflow {
+            connection.execute(
+                "INSERT OR REPLACE INTO meta (key, value) VALUES ('workflow', ?1)",
+                params![serde_json::to_string(&target.workflow)?],
+            )?;
+        }
+
+        for item in &todo_list.items {
+            let synced = &target.items[&item.id];
+            if current.items.get(&item.id) == Some(synced) {
+                continue;
+            }
+            let (_, data) = synced;
+
+            let id = item.id.to_string();
+            connection.prepare_cached(
+                "INSERT OR REPLACE INTO todos (id, parent_id, position, completed, changed_at, data) VALUES (?1, ?2, ?3, ?4, ?5, ?6)",
+            )?.execute(params![
+                id,
+                item.parent_id.map(|parent| parent.to_string()),
+                item.order,
+                item.is_completed(),
+                item.last_changed().timestamp_millis(),
+                data,
+            ])?;
+
+            connection.prepare_cached("DELETE FROM todo_tags WHERE todo_id = ?1")?.execute(params![id])?;
+            for tag in &item.tags {
+                connection.prepare_cached("INSERT OR IGNORE INTO todo_tags (todo_id, tag) VALUES (?1, ?2)")?
+                    .execute(params![id, tag])?;
+            }
+
+            connection.prepare_cached("DELETE FROM todo_text WHERE id = ?1")?.execute(params![id])?;
+            Self::index_text(connection, item)?;
+        }
+
+        for id in current.items.keys().filter(|id| !target.items.contains_key(id)) {
+            let id = id.to_string();
+            connection.prepare_cached("DELETE FROM todos WHERE id = ?1")?.execute(params![id])?;
+            connection.prepare_cached("DELETE FROM todo_tags WHERE todo_id = ?1")?.execute(params![id])?;
+            connection.prepare_cached("DELETE FROM todo_text WHERE id = ?1")?.execute(params![id])?;
+        }
+
+        Ok(())
+    }
+}
+
+impl TodoStore for SqliteStore {
+    fn load(&self) -> Result<TodoList> {
+        let connection = self.connection.borrow();
+        let todo_list = Self::read_all(&connection)
+            .with_context(|| format!("Failed to read todo database: {}", self.file_path.display()))?;
+
+        self.synced.replace(Some(Self::snapshot(&todo_list)?));
+        self.data_version.set(Self::data_version(&connection)?);
+        Ok(todo_list)
+    }
+
+    fn save(&self, todo_list: &TodoList) -> Result<SaveOutcome> {
+        let backups = BackupStore::for_file(&self.file_path)?;
+        if self.file_path.exists() && backups.is_due()? {
+            backups.create(&self.file_path)?;
+        }
+
+        let mut connection = self.connection.borrow_mut();
+        let transaction = connection.transaction_with_behavior(TransactionBehavior::Immediate)
+            .with_context(|| format!("Failed to lock todo database: {}", self.file_path.display()))?;
+
+        let changed_externally = Self::data_version(&transaction)? != self.data_version.get();
+        let (current, merged) = if changed_externally {
+            let theirs = Self::read_all(&transaction)?;
+            let merged = merge_lists(&self.synced_list()?, todo_list, &theirs);
+            (Self::snapshot(&theirs)?, Some(merged))
+        } else {
+            let current = match &*self.synced.borrow() {
+                Some(synced) => synced.clone(),
+                None => Self::snapshot(&TodoList::new())?,
+            };
+            (current, None)
+        };
+
+        let to_write = merged.as_ref().map_or(todo_list, |merged| &merged.list);
+        let target = Self::snapshot(to_write)?;
+
+        Self::write_changes(&transaction, &current, &target, to_write)
+            .with_context(|| format!("Failed to write todo database: {}", self.file_path.display()))?;
+        transaction.execute(
+            "INSERT OR REPLACE INTO meta (key, value) VALUES ('version', ?1)",
+            params![CURRENT_VERSION.to_string()],
+        )?;
+        transaction.commit()
+            .with_context(|| format!("Failed to write todo database: {}", self.file_path.display()))?;
+
+        self.synced.replace(Some(target));
+        self.data_version.set(Self::data_version(&connection)?);
+
+        Ok(match merged {
+            Some(merged) => SaveOutcome::Merged { list: merged.list, conflicts: merged.conflicts },
+            None => SaveOutcome::Written,
+        })
+    }
+
+    fn changed_externally(&self) -> Result<bool> {
+        let data_version = Self::data_version(&self.connection.borrow())?;
+        Ok(data_version != self.data_version.get())
+    }
+
+    fn get_file_path(&self) -> &Path {
+        &self.file_path
+    }
+
+    fn query(&self, query: &TodoQuery) -> Result<Vec<TodoItem>> {
+        let mut sql = String::from("SELECT position, data FROM todos WHERE 1 = 1");
+        let mut values: Vec<Box<dyn ToSql>> = Vec::new();
+
+        if let Some(completed) = query.completed {
+            sql.push_str(" AND completed = ?");
+            values.push(Box::new(completed));
+        }
+        if let Some(since) = query.changed_since {
+            sql.push_str(" AND changed_at >= ?");
+            values.push(Box::new(since.timestamp_millis()));
+        }
+        if let Some(tag) = &query.tag {
+            sql.push_str(" AND id IN (SELECT todo_id FROM todo_tags WHERE tag = ?)");
+            values.push(Box::new(tag.clone()));
+        }
+        if let Some(search) = query.search.as_deref().filter(|search| search.chars().count() >= MIN_INDEXED_SEARCH) {
+            sql.push_str(" AND id IN (SELECT id FROM todo_text WHERE todo_text MATCH ?)");
+            values.push(Box::new(format!("\"{}\"", search.to_lowercase().replace('"', "\"\""))));
+        }
+        sql.push_str(" ORDER BY position");
+
+        let connection = self.connection.borrow();
+        let mut statement = connection.prepare(&sql)?;
+        let items = statement
+            .query_map(rusqlite::params_from_iter(values.iter()), Self::read_row)?
+            .map(|row| row?)
+            .collect::<Result<Vec<_>>>()
+            .with_context(|| format!("Failed to query todo database: {}", self.file_path.display()))?;
+        Ok(items.into_iter().filter(|item| query.matches(item)).collect())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::path::Path;
+
+    struct Scratch(PathBuf);
+
+    impl Scratch {
+        fn new() -> Self {
+            Self(std::env::temp_dir().join(format!("termtask-sqlite-{}", Uuid::new_v4())))
+        }
+
+        fn file(&self) -> PathBuf {
+            self.0.join("todos.db")
+        }
+    }
+
+    impl Drop for Scratch {
+        fn drop(&mut self) {
+            let _ = fs::remove_dir_all(&self.0);
+        }
+    }
+
+    fn titles(todos: &TodoList) -> Vec<&str> {
+        todos.items.iter().map(|item| item.title.as_str()).collect()
+    }
+
+    fn sample(path: &Path) -> (SqliteStore, TodoList) {
+        let store = SqliteStore::open(path.to_path_buf()).unwrap();
+        let mut todos = store.load().unwrap();
+        let report = todos.add_todo("Write Report".to_string(), Some("For the board".to_string()));
+        let groceries = todos.add_todo("Groceries".to_string(), None);
+        todos.add_todo("Go".to_string(), Some("to the gym".to_string()));
+        todos.get_todo_by_id_mut(&report).unwrap().tags.push("Work".to_string());
+        todos.toggle_todo(&groceries);
+        store.save(&todos).unwrap();
+        (store, todos)
+    }
+
+    #[test]
+    fn saves_and_reloads() {
+        let scratch = Scratch::new();
+        let (_, todos) = sample(&scratch.file());
+
+        let loaded = SqliteStore::open(scratch.file()).unwrap().load().unwrap();
+        assert_eq!(loaded.items, todos.items);
+        assert_eq!(loaded.workflow, todos.workflow);
+    }
+
+    #[test]
+    fn deletes_and_updates_rows() {
+        let scratch = Scratch::new();
+        let (store, mut todos) = sample(&scratch.file());
+
+        let groceries = todos.items[1].id;
+        todos.remove_todo(&groceries);
+        todos.items[0].title = "Write the report".to_string();
+        assert!(matches!(store.save(&todos).unwrap(), SaveOutcome::Written));
+
+        let loaded = SqliteStore::open(scratch.file()).unwrap().load().unwrap();
+        assert_eq!(titles(&loaded), vec!["Write the report", "Go"]);
+        let query = TodoQuery { search: Some("grocer".to_string()), ..TodoQuery::default() };
+        assert!(store.query(&query).unwrap().is_empty());
+    }
+
+    #[test]
+    fn keeps_a_reorder() {
+        let scratch = Scratch::new();
+        let (store, mut todos) = sample(&scratch.file());
+
+        let (first, last) = (todos.items[0].id, todos.items[2].id);
+        todos.move_todo(&last, &first, false);
+        store.save(&todos).unwrap();
+
+        let loaded = SqliteStore::open(scratch.file()).unwrap().load().unwrap();
+        assert_eq!(titles(&loaded), vec!["Go", "Write Report", "Groceries"]);
+    }
+
+    #[test]
+    fn queries_match_the_json_store() {
+        let scratch = Scratch::new();
+        let (store, todos) = sample(&scratch.file());
+
+        let queries = [
+            TodoQuery { search: Some("PORT".to_string()), ..TodoQuery::default() },
+            TodoQuery { search: Some("go".to_string()), ..TodoQuery::default() },
+            TodoQuery { search: Some("the b".to_string()), ..TodoQuery::default() },
+            TodoQuery { search: Some("work".to_string()), completed: Some(false), ..TodoQuery::default() },
+            TodoQuery { tag: Some("WORK".to_string()), ..TodoQuery::default() },
+            TodoQuery { completed: Some(true), ..TodoQuery::default() },
+            TodoQuery { changed_since: Some(chrono::Utc::now() + chrono::Duration::hours(1)), ..TodoQuery::default() },
+        ];
+        for query in queries {
+            let expected: Vec<&TodoItem> = todos.items.iter().filter(|item| query.matches(item)).collect();
+            let found = store.query(&query).unwrap();
+            assert_eq!(found.iter().collect::<Vec<_>>(), expected, "{:?}", query);
+        }
+    }
+
+    #[test]
+    fn merges_changes_from_another_instance() {
+        let scratch = Scratch::new();
+        let (ours, mut our_todos) = sample(&scratch.file());
+
+        let theirs = SqliteStore::open(scratch.file()).unwrap();
+        let mut their_todos = theirs.load().unwrap();
+        their_todos.add_todo("Call the bank".to_string(), None);
+        theirs.save(&their_todos).unwrap();
+
+        assert!(ours.changed_externally().unwrap());
+        our_todos.items[0].title = "Write the report".to_string();
+        match ours.save(&our_todos).unwrap() {
+            SaveOutcome::Merged { list, conflicts } => {
+                assert_eq!(conflicts, 0);
+                assert_eq!(titles(&list), vec!["Write the report", "Groceries", "Go", "Call the bank"]);
+            }
+            SaveOutcome::Written => panic!("the other instance's todo was overwritten"),
+        }
+        assert!(!ours.changed_externally().unwrap());
+
+        let loaded = theirs.load().unwrap();
+        assert_eq!(titles(&loaded), vec!["Write the report", "Groceries", "Go", "Call the bank"]);
+    }
+}
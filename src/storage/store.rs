@@ -0,0 +1,133 @@
+use crate::models::{TodoItem, TodoList};
+use crate::storage::file_store::SaveOutcome;
+use crate::storage::json_store::JsonStore;
+use crate::storage::markdown_store::MarkdownStore;
+#[cfg(feature = "sqlite")]
+use crate::storage::sqlite_store::SqliteStore;
+use crate::storage::toml_store::TomlStore;
+#[cfg(not(feature = "sqlite"))]
+use anyhow::bail;
+use anyhow::Result;
+use chrono::{DateTime, Utc};
+use std::path::{Path, PathBuf};
+
+#[derive(Debug, Clone, Default)]
+pub struct TodoQuery {
+    pub completed: Option<bool>,
+    pub search: Option<String>,
+    pub tag: Option<String>,
+    pub changed_since: Option<DateTime<Utc>>,
+}
+
+impl TodoQuery {
+    pub fn matches(&self, item: &TodoItem) -> bool {
+        if self.completed.is_some_and(|completed| item.is_completed() != completed) {
+            return false;
+        }
+
+        if self.changed_since.is_some_and(|since| item.last_changed() < since) {
+            return false;
+        }
+
+        if self.tag.as_deref().is_some_and(|tag| !item.has_tag(tag)) {
+            return false;
+        }
+
+        if let Some(search) = &self.search {
+            let search = search.to_lowercase();
+            let found = item.title.to_lowercase().contains(&search)
+                || item.description.as_deref().is_some_and(|description| description.to_lowercase().contains(&search))
+                || item.tags.iter().any(|tag| tag.to_lowercase().contains(&search));
+            if !found {
+                return false;
+            }
+        }
+
+        true
+    }
+}
+
+pub trait TodoStore {
+    fn load(&self) -> Result<TodoList>;
+    fn save(&self, todo_list: &TodoList) -> Result<SaveOutcome>;
+    fn changed_externally(&self) -> Result<bool>;
+    fn get_file_path(&self) -> &Path;
+
+    fn query(&self, query: &TodoQuery) -> Result<Vec<TodoItem>> {
+        let todo_list = self.load()?;
+        Ok(todo_list.items.into_iter().filter(|item| query.matches(item)).collect())
+    }
+}
+
+pub fn open_store(file_path: PathBuf) -> Result<Box<dyn TodoStore>> {
+    let extension = file_path.extension()
+        .and_then(|extension| extension.to_str())
+        .map(|extension| extension.to_ascii_lowercase());
+
+    Ok(match extension.as_deref() {
+        Some("md" | "markdown") => Box::new(MarkdownStore::new(file_path)),
+        Some("toml") => Box::new(TomlStore::new(file_path)),
+        #[cfg(feature = "sqlite")]
+        Some("db" | "sqlite" | "sqlite3") => Box::new(SqliteStore::open(file_path)?),
+        #[cfg(not(feature = "sqlite"))]
+        Some("db" | "sqlite" | "sqlite3") => {
+            bail!("SQLite storage is not available in this build of TermTask. Rebuild with `--features sqlite` to use {}", file_path.display())
+        }
+        _ => Box::new(JsonStore::new(file_path)),
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::Duration;
+    use std::fs;
+    use uuid::Uuid;
+
+    #[test]
+    fn open_store_picks_the_format_from_the_extension() {
+        let directory = std::env::temp_dir().join(format!("termtask-store-{}", Uuid::new_v4()));
+        let mut todos = TodoList::new();
+        todos.add_todo("Pick a format".to_string(), None);
+
+        for (name, marker) in [("todos.json", "\"items\""), ("todos.TOML", "[[items]]"), ("todos.md", "- [ ] Pick a format"), ("todos", "\"items\"")] {
+            let path = directory.join(name);
+            let store = open_store(path.clone()).unwrap();
+            store.save(&todos).unwrap();
+            assert!(fs::read_to_string(&path).unwrap().contains(marker), "{}", name);
+            assert_eq!(store.load().unwrap().items[0].title, "Pick a format");
+        }
+
+        #[cfg(not(feature = "sqlite"))]
+        assert!(open_store(directory.join("todos.db")).is_err());
+
+        fs::remove_dir_all(&directory).unwrap();
+    }
+
+    #[test]
+    fn queries_filter_like_the_list_command() {
+        let mut todos = TodoList::new();
+        let report = todos.add_todo("Write Report".to_string(), Some("For the Board".to_string()));
+        let groceries = todos.add_todo("Groceries".to_string(), None);
+        todos.get_todo_by_id_mut(&groceries).unwrap().tags.push("Home".to_string());
+        todos.toggle_todo(&groceries);
+        let item = |id: &Uuid| todos.get_todo_by_id(id).unwrap();
+
+        let query = TodoQuery { search: Some("port".to_string()), ..TodoQuery::default() };
+        assert!(query.matches(item(&report)) && !query.matches(item(&groceries)));
+
+        let query = TodoQuery { search: Some("BOARD".to_string()), ..TodoQuery::default() };
+        assert!(query.matches(item(&report)));
+
+        let query = TodoQuery { tag: Some("home".to_string()), completed: Some(true), ..TodoQuery::default() };
+        assert!(query.matches(item(&groceries)) && !query.matches(item(&report)));
+
+        let query = TodoQuery { search: Some("om".to_string()), completed: Some(false), ..TodoQuery::default() };
+        assert!(!query.matches(item(&groceries)));
+
+        let query = TodoQuery { changed_since: Some(Utc::now() + Duration::hours(1)), ..TodoQuery::default() };
+        assert!(!query.matches(item(&report)));
+        let query = TodoQuery { changed_since: Some(Utc::now() - Duration::hours(1)), ..TodoQuery::default() };
+        assert!(query.matches(item(&report)));
+    }
+}
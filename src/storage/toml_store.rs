@@ -0,0 +1,79 @@
+use crate::models::TodoList;
+use crate::storage::file_store::{DocumentFormat, FileStore};
+use anyhow::Result;
+use serde_json::Value;
+
+#[derive(Default)]
+pub struct TomlFormat;
+
+impl DocumentFormat for TomlFormat {
+    fn parse(&self, content: &str) -> Result<Value> {
+        Ok(toml::from_str(content)?)
+    }
+
+    fn serialize(&self, todo_list: &TodoList) -> Result<String> {
+        let mut todo_list = todo_list.clone();
+        for item in &mut todo_list.items {
+            item.extensions.values_mut().for_each(remove_nulls);
+        }
+        Ok(toml::to_string_pretty(&todo_list)?)
+    }
+}
+
+/// TOML has no null, so extension fields that are null (e.g. from an imported file) are left
+/// out, just like the todo's own empty fields.
+fn remove_nulls(value: &mut Value) {
+    match value {
+        Value::Object(fields) => {
+            fields.retain(|_, value| !value.is_null());
+            fields.values_mut().for_each(remove_nulls);
+        }
+        Value::Array(values) => {
+            values.retain(|value| !value.is_null());
+            values.iter_mut().for_each(remove_nulls);
+        }
+        _ => {}
+    }
+}
+
+pub type TomlStore = FileStore<TomlFormat>;
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::storage::store::open_store;
+    use serde_json::json;
+    use std::fs;
+    use uuid::Uuid;
+
+    #[test]
+    fn round_trips_through_a_file() {
+        let directory = std::env::temp_dir().join(format!("termtask-toml-{}", Uuid::new_v4()));
+        let path = directory.join("todos.toml");
+        let store = open_store(path.clone()).unwrap();
+
+        let mut todos = TodoList::new();
+        let id = todos.add_todo("Write \"docs\"".to_string(), Some("Two\nlines".to_string()));
+        todos.add_todo("Child".to_string(), None);
+        todos.items[1].parent_id = Some(id);
+        todos.items[0].tags.push("work".to_string());
+        todos.items[0].extensions.insert(
+            "taskwarrior".to_string(),
+            json!({ "project": "docs", "wait": null, "annotations": [null, { "entry": null, "description": "kept" }] }),
+        );
+        store.save(&todos).unwrap();
+        assert!(fs::read_to_string(&path).unwrap().contains("[[items]]"));
+
+        let loaded = open_store(path).unwrap().load().unwrap();
+        assert_eq!(loaded.items.len(), 2);
+        assert_eq!(loaded.items[0].title, "Write \"docs\"");
+        assert_eq!(loaded.items[0].description.as_deref(), Some("Two\nlines"));
+        assert_eq!(loaded.items[1].parent_id, Some(id));
+        assert_eq!(
+            loaded.items[0].extensions["taskwarrior"],
+            json!({ "project": "docs", "annotations": [{ "description": "kept" }] })
+        );
+
+        fs::remove_dir_all(&directory).unwrap();
+    }
+}
@@ -1,9 +1,11 @@
+use crate::models::{due_date_input, format_due_date, format_duration, format_tags, parse_due_date, parse_tags, Recurrence};
 use crate::ui::{
-    components::ConfirmationAction,
+    components::{ConfirmationAction, TodoListComponent},
     AppEvent,
 };
-use crossterm::event::{KeyCode, KeyEvent, KeyModifiers};
+use crossterm::event::{KeyCode, KeyEvent, KeyModifiers, MouseButton, MouseEvent, MouseEventKind};
 use anyhow::Result;
+use uuid::Uuid;
 
 use super::App;
 
@@ -76,6 +78,14 @@ impl App {
                                 Some(AppEvent::Quit)
                             }
                             KeyCode::Char('q') => Some(AppEvent::Quit),
+                            KeyCode::Char('r') if key.modifiers.contains(KeyModifiers::CONTROL) => Some(AppEvent::Redo),
+                            KeyCode::Char('u') => Some(AppEvent::Undo),
+                            KeyCode::Up if key.modifiers.contains(KeyModifiers::ALT) => Some(AppEvent::MoveTodoUp),
+                            KeyCode::Down if key.modifiers.contains(KeyModifiers::ALT) => Some(AppEvent::MoveTodoDown),
+                            KeyCode::Left if key.modifiers.contains(KeyModifiers::SHIFT) => Some(AppEvent::MoveStatusBackward),
+                            KeyCode::Right if key.modifiers.contains(KeyModifiers::SHIFT) => Some(AppEvent::MoveStatusForward),
+                            KeyCode::Char('K') => Some(AppEvent::MoveTodoUp),
+                            KeyCode::Char('J') => Some(AppEvent::MoveTodoDown),
                             KeyCode::Up | KeyCode::Char('k') => Some(AppEvent::Up),
                             KeyCode::Down | KeyCode::Char('j') => Some(AppEvent::Down),
                             KeyCode::Enter => Some(AppEvent::Enter),
@@ -97,6 +107,16 @@ impl App {
                             KeyCode::Char('h') | KeyCode::Left => Some(AppEvent::GoToLeftPane),
                             KeyCode::Char('l') | KeyCode::Right => Some(AppEvent::GoToRightPane),
                             KeyCode::Char('f') => Some(AppEvent::ToggleZoom),
+                            KeyCode::Char(']') => Some(AppEvent::RaisePriority),
+                            KeyCode::Char('[') => Some(AppEvent::LowerPriority),
+                            KeyCode::Char('#') => Some(AppEvent::ToggleTagSidebar),
+                            KeyCode::Char('>') => Some(AppEvent::IndentTodo),
+                            KeyCode::Char('<') => Some(AppEvent::OutdentTodo),
+                            KeyCode::Char('A') => Some(AppEvent::AddSubtask),
+                            KeyCode::Char('T') => Some(AppEvent::ToggleTimer),
+                            KeyCode::Char('b') => Some(AppEvent::ToggleBoardView),
+                            KeyCode::Char('H') => Some(AppEvent::MoveStatusBackward),
+                            KeyCode::Char('L') => Some(AppEvent::MoveStatusForward),
                             KeyCode::Char('z') if self.config.ui.vim_mode => Some(AppEvent::Char('z')),
                             KeyCode::Char('a') if self.config.ui.vim_mode => Some(AppEvent::Char('a')),
                             _ => None,
@@ -128,6 +148,10 @@ impl App {
             return self.handle_modal_event(event);
         }
 
+        if self.tag_sidebar.focused {
+            return self.handle_tag_sidebar_event(event);
+        }
+
         match self.mode {
             super::AppMode::Normal => self.handle_normal_event(event)?,
         }
@@ -136,12 +160,61 @@ impl App {
     }
 
     pub(super) fn handle_normal_event(&mut self, event: AppEvent) -> Result<()> {
-        let current_list = if self.active_pane { &mut self.active_list } else { &mut self.completed_list };
+        if self.board_view {
+            match event {
+                AppEvent::SwitchPane => {
+                    self.board_column = (self.board_column + 1) % self.board_columns.len().max(1);
+                    return Ok(());
+                }
+                AppEvent::GoToLeftPane => {
+                    if self.board_column == 0 && self.tag_sidebar.visible {
+                        self.tag_sidebar.focused = true;
+                    }
+                    self.board_column = self.board_column.saturating_sub(1);
+                    return Ok(());
+                }
+                AppEvent::GoToRightPane => {
+                    self.board_column = (self.board_column + 1).min(self.board_columns.len().saturating_sub(1));
+                    return Ok(());
+                }
+                AppEvent::ToggleZoom => return Ok(()),
+                _ => {}
+            }
+        }
+
+        let current_list = if self.board_view {
+            &mut self.board_columns[self.board_column]
+        } else if self.active_pane {
+            &mut self.active_list
+        } else {
+            &mut self.completed_list
+        };
 
         match event {
-            AppEvent::Quit => self.should_quit = true,
+            AppEvent::Quit => self.request_quit(),
             AppEvent::Up => current_list.select_previous(&self.todos),
             AppEvent::Down => current_list.select_next(&self.todos),
+            AppEvent::Undo => self.undo()?,
+            AppEvent::Redo => self.redo()?,
+            AppEvent::ToggleBoardView => {
+                self.board_view = !self.board_view;
+                self.config.ui.board_view = self.board_view;
+                self.sync_board_columns();
+                self.validate_selections();
+                let _ = self.save_config();
+            }
+            AppEvent::MoveStatusBackward | AppEvent::MoveStatusForward => {
+                if let Some(todo) = current_list.get_selected_todo(&self.todos) {
+                    let todo_id = todo.id;
+                    let todo_title = todo.title.clone();
+                    if let Some(status) = self.todos.shift_status(&todo_id, event == AppEvent::MoveStatusForward) {
+                        self.save_todos()?;
+                        self.follow_todo(&todo_id);
+                        self.refresh_tag_filter();
+                        self.toast_manager.info(format!("{} → {}", todo_title, status));
+                    }
+                }
+            }
             AppEvent::SwitchPane => {
                 self.active_pane = !self.active_pane;
                 if self.zoomed_pane.is_some() {
@@ -149,6 +222,9 @@ impl App {
                 }
             }
             AppEvent::GoToLeftPane => {
+                if self.active_pane && self.tag_sidebar.visible {
+                    self.tag_sidebar.focused = true;
+                }
                 self.active_pane = true;
                 if self.zoomed_pane.is_some() {
                     self.zoomed_pane = Some(true);
@@ -181,6 +257,27 @@ impl App {
             AppEvent::ToggleZoom => {
                 self.toggle_zoom();
             }
+            AppEvent::ToggleTagSidebar => {
+                self.tag_sidebar.toggle();
+                self.tag_sidebar.validate_selection(&self.todos);
+            }
+            AppEvent::RaisePriority | AppEvent::LowerPriority => {
+                if let Some(todo) = current_list.get_selected_todo(&self.todos) {
+                    let todo_id = todo.id;
+                    let priority = if event == AppEvent::RaisePriority {
+                        todo.priority.raise()
+                    } else {
+                        todo.priority.lower()
+                    };
+
+                    if priority != todo.priority {
+                        self.todos.set_priority(&todo_id, priority);
+                        current_list.select_by_id(&self.todos, &todo_id);
+                        self.save_todos()?;
+                        self.toast_manager.info(format!("Priority: {}", priority.name()));
+                    }
+                }
+            }
             AppEvent::Delete => {
                 if let Some(todo) = current_list.get_selected_todo(&self.todos) {
                     let action = ConfirmationAction::Delete(todo.title.clone());
@@ -188,15 +285,86 @@ impl App {
                 }
             }
             AppEvent::AddTodo => {
+                self.adding_child_of = None;
                 self.add_todo_modal.open();
             }
+            AppEvent::AddSubtask => {
+                if let Some(todo) = current_list.get_selected_todo(&self.todos) {
+                    self.adding_child_of = Some(todo.id);
+                    self.add_todo_modal.open();
+                }
+            }
+            AppEvent::IndentTodo => {
+                if let Some(todo) = current_list.get_selected_todo(&self.todos) {
+                    let todo_id = todo.id;
+                    if let Some(sibling_id) = current_list.get_previous_sibling(&self.todos, &todo_id) {
+                        self.todos.set_parent(&todo_id, Some(sibling_id));
+                        self.todos.set_expanded(&sibling_id, true);
+                        current_list.select_by_id(&self.todos, &todo_id);
+                        self.save_todos()?;
+                    }
+                }
+            }
+            AppEvent::OutdentTodo => {
+                if let Some(todo) = current_list.get_selected_todo(&self.todos) {
+                    let todo_id = todo.id;
+                    if let Some(parent_id) = todo.parent_id {
+                        let grandparent_id = self.todos.get_todo_by_id(&parent_id).and_then(|parent| parent.parent_id);
+                        self.todos.set_parent(&todo_id, grandparent_id);
+                        current_list.select_by_id(&self.todos, &todo_id);
+                        self.save_todos()?;
+                    }
+                }
+            }
+            AppEvent::MoveTodoUp | AppEvent::MoveTodoDown => {
+                if let Some(todo) = current_list.get_selected_todo(&self.todos) {
+                    let todo_id = todo.id;
+                    let moving_up = event == AppEvent::MoveTodoUp;
+                    let sibling_id = if moving_up {
+                        current_list.get_previous_sibling(&self.todos, &todo_id)
+                    } else {
+                        current_list.get_next_sibling(&self.todos, &todo_id)
+                    };
+
+                    if let Some(sibling_id) = sibling_id {
+                        if self.can_reorder(&todo_id, &sibling_id) {
+                            self.move_todo(&todo_id, &sibling_id, !moving_up)?;
+                        } else {
+                            self.toast_manager.warning("Disable priority sorting to reorder across priorities".to_string());
+                        }
+                    }
+                }
+            }
+            AppEvent::ToggleTimer => {
+                if let Some(todo) = current_list.get_selected_todo(&self.todos) {
+                    let todo_id = todo.id;
+                    let todo_title = todo.title.clone();
+
+                    if todo.is_completed() {
+                        self.toast_manager.warning("Reopen the todo to track time on it".to_string());
+                    } else if todo.is_timer_running() {
+                        if let Some(duration) = self.todos.stop_timer(&todo_id) {
+                            self.save_todos()?;
+                            self.toast_manager.info(format!("Timer stopped: {} ({})", todo_title, format_duration(&duration)));
+                        }
+                    } else {
+                        let previous = self.todos.get_running_timer().map(|running| running.title.clone());
+                        self.todos.start_timer(&todo_id);
+                        self.save_todos()?;
+                        match previous {
+                            Some(previous) => self.toast_manager.info(format!("Timer switched from {} to {}", previous, todo_title)),
+                            None => self.toast_manager.info(format!("Timer started: {}", todo_title)),
+                        }
+                    }
+                }
+            }
             AppEvent::ToggleTheme => {
                 self.config.theme = self.config.theme.next();
                 self.save_config()?;
                 self.toast_manager.info(format!("Theme: {}", self.config.theme.name()));
             }
             AppEvent::OpenSettings => {
-                self.settings.open(&self.config.theme, &self.config.ui.date_format, self.config.ui.vim_mode, self.config.ui.compact_mode);
+                self.settings.open(&self.config.theme, &self.config.ui.date_format, self.config.ui.vim_mode, self.config.ui.compact_mode, self.config.ui.sort_by_priority);
             }
             AppEvent::ShowHelp => {
                 self.help_modal.open();
@@ -204,12 +372,25 @@ impl App {
             AppEvent::EditTodo => {
                 if let Some(todo) = current_list.get_selected_todo(&self.todos) {
                     self.editing_todo_id = Some(todo.id);
-                    self.add_todo_modal.open_with_data(&todo.title, todo.description.as_deref());
+                    self.adding_child_of = None;
+                    let due = todo.due_at.as_ref().map(due_date_input).unwrap_or_default();
+                    let tags = format_tags(&todo.tags);
+                    let repeat = todo.recurrence.as_ref().map(Recurrence::describe).unwrap_or_default();
+                    self.add_todo_modal.open_with_data(&todo.title, todo.description.as_deref(), &due, &tags, &repeat);
                 }
             }
             AppEvent::PreviewTodo => {
                 if let Some(todo) = current_list.get_selected_todo(&self.todos) {
-                    self.preview_modal.open(&todo.title, todo.description.as_deref());
+                    let mut details = Vec::new();
+                    if !todo.time_entries.is_empty() {
+                        let running = if todo.is_timer_running() { ", timer running" } else { "" };
+                        details.push(format!("Time spent: {} ({} session(s){})",
+                            format_duration(&todo.total_time_spent()), todo.time_entries.len(), running));
+                    }
+                    if let Some(commit) = &todo.closed_by {
+                        details.push(format!("Closed by commit {}: {}", commit.short_hash(), commit.summary));
+                    }
+                    self.preview_modal.open(&todo.title, todo.description.as_deref(), details);
                 }
             }
             AppEvent::IncreaseSplit => {
@@ -244,8 +425,245 @@ impl App {
         Ok(())
     }
 
+    pub(super) fn request_quit(&mut self) {
+        match self.todos.get_running_timer() {
+            Some(todo) => {
+                let action = ConfirmationAction::QuitWithRunningTimer(todo.title.clone());
+                self.confirmation_modal.open(action);
+            }
+            None => self.should_quit = true,
+        }
+    }
+
+    pub(super) fn focused_pane(&self) -> usize {
+        if self.board_view {
+            self.board_column
+        } else if self.active_pane {
+            0
+        } else {
+            1
+        }
+    }
+
+    pub(super) fn focus_pane(&mut self, pane: usize) {
+        if self.board_view {
+            self.board_column = pane.min(self.board_columns.len().saturating_sub(1));
+        } else {
+            self.active_pane = pane == 0;
+        }
+        self.tag_sidebar.focused = false;
+    }
+
+    fn pane_list(&self, pane: usize) -> Option<&TodoListComponent> {
+        if self.board_view {
+            self.board_columns.get(pane)
+        } else {
+            [&self.active_list, &self.completed_list].get(pane).copied()
+        }
+    }
+
+    fn pane_list_mut(&mut self, pane: usize) -> Option<&mut TodoListComponent> {
+        if self.board_view {
+            self.board_columns.get_mut(pane)
+        } else if pane == 0 {
+            Some(&mut self.active_list)
+        } else if pane == 1 {
+            Some(&mut self.completed_list)
+        } else {
+            None
+        }
+    }
+
+    fn pane_at(&self, column: u16, row: u16) -> Option<(usize, usize)> {
+        let pane_count = if self.board_view { self.board_columns.len() } else { 2 };
+        (0..pane_count).find_map(|pane| {
+            self.pane_list(pane)
+                .and_then(|list| list.row_at(column, row))
+                .map(|index| (pane, index))
+        })
+    }
+
+    pub(super) fn select_in_pane(&mut self, pane: usize, id: &Uuid) {
+        let index = self.pane_list(pane)
+            .and_then(|list| list.visible_items(&self.todos).iter().position(|todo| &todo.id == id));
+        if let (Some(index), Some(list)) = (index, self.pane_list_mut(pane)) {
+            list.state.select(Some(index));
+        }
+    }
+
+    pub(super) fn validate_selections(&mut self) {
+        self.active_list.validate_selection(&self.todos);
+        self.completed_list.validate_selection(&self.todos);
+        for column in self.board_columns.iter_mut() {
+            column.validate_selection(&self.todos);
+        }
+    }
+
+    pub(super) fn sync_board_columns(&mut self) {
+        let in_sync = self.board_columns.len() == self.todos.workflow.len()
+            && self.board_columns.iter().zip(&self.todos.workflow)
+                .all(|(column, status)| column.status_filter.as_ref() == Some(status));
+        if in_sync {
+            return;
+        }
+
+        self.board_columns = self.todos.workflow.iter()
+            .map(|status| {
+                let mut column = TodoListComponent::new_board_column(status);
+                column.sort_by_priority = self.config.ui.sort_by_priority;
+                column.tag_filter = self.active_list.tag_filter.clone();
+                column
+            })
+            .collect();
+        self.board_column = self.board_column.min(self.board_columns.len().saturating_sub(1));
+    }
+
+    pub(super) fn follow_todo(&mut self, id: &Uuid) {
+        if self.board_view {
+            let column = self.todos.get_todo_by_id(id).and_then(|todo| self.todos.state_index(&todo.status));
+            if let Some(column) = column {
+                self.board_column = column;
+            }
+        }
+        self.select_in_pane(self.focused_pane(), id);
+        self.validate_selections();
+    }
+
+    pub(super) fn can_reorder(&self, id: &Uuid, target_id: &Uuid) -> bool {
+        let sort_by_priority = self.pane_list(self.focused_pane()).is_some_and(|list| list.sort_by_priority);
+        match (self.todos.get_todo_by_id(id), self.todos.get_todo_by_id(target_id)) {
+            (Some(todo), Some(target)) => {
+                todo.parent_id == target.parent_id
+                    && (!sort_by_priority || todo.priority == target.priority)
+            }
+            _ => false,
+        }
+    }
+
+    pub(super) fn move_todo(&mut self, id: &Uuid, target_id: &Uuid, after: bool) -> Result<()> {
+        if self.todos.move_todo(id, target_id, after) {
+            self.select_in_pane(self.focused_pane(), id);
+            self.save_todos()?;
+        }
+        Ok(())
+    }
+
+    pub(super) fn handle_mouse_event(&mut self, mouse: MouseEvent) -> Result<()> {
+        if self.confirmation_modal.active || self.settings.active || self.help_modal.active
+            || self.preview_modal.active || self.add_todo_modal.active()
+        {
+            return Ok(());
+        }
+
+        let hit = self.pane_at(mouse.column, mouse.row);
+
+        match mouse.kind {
+            MouseEventKind::Down(MouseButton::Left) => {
+                self.drag_source = None;
+                if let Some((pane, index)) = hit {
+                    self.focus_pane(pane);
+                    if let Some(list) = self.pane_list_mut(pane) {
+                        list.state.select(Some(index));
+                    }
+                    self.drag_source = self.pane_list(pane)
+                        .and_then(|list| list.get_selected_todo(&self.todos))
+                        .map(|todo| todo.id);
+                }
+            }
+            MouseEventKind::Drag(MouseButton::Left) => {
+                if let (Some(source_id), Some((pane, index))) = (self.drag_source, hit) {
+                    if pane != self.focused_pane() {
+                        if self.board_view {
+                            let status = self.todos.workflow[pane].clone();
+                            self.todos.set_status(&source_id, &status);
+                            self.follow_todo(&source_id);
+                            self.drag_moved = true;
+                        }
+                        return Ok(());
+                    }
+
+                    let (source_index, target_id) = match self.pane_list(pane) {
+                        Some(list) => (
+                            list.state.selected().unwrap_or(index),
+                            list.visible_items(&self.todos).get(index).map(|todo| todo.id),
+                        ),
+                        None => return Ok(()),
+                    };
+
+                    if let Some(target_id) = target_id {
+                        if target_id != source_id && self.can_reorder(&source_id, &target_id) {
+                            self.todos.move_todo(&source_id, &target_id, index > source_index);
+                            self.select_in_pane(pane, &source_id);
+                            self.drag_moved = true;
+                        }
+                    }
+                }
+            }
+            MouseEventKind::Up(MouseButton::Left) => {
+                self.drag_source = None;
+                if self.drag_moved {
+                    self.drag_moved = false;
+                    self.save_todos()?;
+                }
+            }
+            MouseEventKind::ScrollUp | MouseEventKind::ScrollDown => {
+                if let Some((pane, _)) = hit {
+                    self.focus_pane(pane);
+                    let event = if mouse.kind == MouseEventKind::ScrollUp { AppEvent::Up } else { AppEvent::Down };
+                    self.handle_normal_event(event)?;
+                }
+            }
+            _ => {}
+        }
+        Ok(())
+    }
+
+    pub(super) fn handle_tag_sidebar_event(&mut self, event: AppEvent) -> Result<()> {
+        match event {
+            AppEvent::Quit => self.request_quit(),
+            AppEvent::Up => self.tag_sidebar.select_previous(&self.todos),
+            AppEvent::Down => self.tag_sidebar.select_next(&self.todos),
+            AppEvent::Enter | AppEvent::Space => {
+                let tag = self.tag_sidebar.apply_selection(&self.todos);
+                self.set_tag_filter(tag);
+            }
+            AppEvent::GoToRightPane | AppEvent::SwitchPane | AppEvent::Escape => {
+                self.tag_sidebar.focused = false;
+                self.active_pane = true;
+            }
+            AppEvent::ToggleTagSidebar => {
+                self.tag_sidebar.toggle();
+            }
+            AppEvent::ShowHelp => self.help_modal.open(),
+            _ => {}
+        }
+        Ok(())
+    }
+
+    pub(super) fn set_tag_filter(&mut self, tag: Option<String>) {
+        self.active_list.tag_filter = tag.clone();
+        self.completed_list.tag_filter = tag.clone();
+        for column in self.board_columns.iter_mut() {
+            column.tag_filter = tag.clone();
+        }
+        self.validate_selections();
+    }
+
+    pub(super) fn refresh_tag_filter(&mut self) {
+        self.tag_sidebar.validate_selection(&self.todos);
+        if self.tag_sidebar.selected_tag != self.active_list.tag_filter {
+            self.set_tag_filter(self.tag_sidebar.selected_tag.clone());
+        }
+    }
+
     pub(super) fn handle_vim_command(&mut self, c: char) {
-        let current_list = if self.active_pane { &mut self.active_list } else { &mut self.completed_list };
+        let current_list = if self.board_view {
+            &mut self.board_columns[self.board_column]
+        } else if self.active_pane {
+            &mut self.active_list
+        } else {
+            &mut self.completed_list
+        };
 
         match (self.vim_prefix, c) {
             (None, 'z') => {
@@ -318,12 +736,12 @@ impl App {
                 self.confirmation_modal.toggle_selection();
             }
             AppEvent::Enter => {
-                if self.confirmation_modal.is_yes_selected() {
-                    if let Some(action) = self.confirmation_modal.action.clone() {
-                        self.execute_confirmation_action(action)?;
-                    }
-                }
+                let action = self.confirmation_modal.action.clone();
+                let confirmed = self.confirmation_modal.is_yes_selected();
                 self.confirmation_modal.close();
+                if let (true, Some(action)) = (confirmed, action) {
+                    self.execute_confirmation_action(action)?;
+                }
             }
             _ => {}
         }
@@ -337,35 +755,74 @@ impl App {
                 self.editing_todo_id = None;
                 self.toast_manager.info("Changes discarded".to_string());
             }
+            ConfirmationAction::QuitWithRunningTimer(_) => {
+                self.todos.stop_all_timers();
+                self.save_todos()?;
+                self.should_quit = true;
+            }
+            ConfirmationAction::CompleteSubtasks(parent_id, _, _) => {
+                let mut completed = 0;
+                for id in self.todos.get_descendant_ids(&parent_id) {
+                    if self.todos.get_todo_by_id(&id).is_some_and(|todo| !todo.is_completed()) {
+                        self.todos.toggle_todo(&id);
+                        completed += 1;
+                    }
+                }
+                self.save_todos()?;
+                self.toast_manager.success_with_undo(format!("Completed {} subtask(s)", completed));
+                self.validate_selections();
+            }
             ConfirmationAction::Complete(_) | ConfirmationAction::Delete(_) => {
-                let current_list = if self.active_pane { &self.active_list } else { &self.completed_list };
+                let current_list = if self.board_view {
+                    &self.board_columns[self.board_column]
+                } else if self.active_pane {
+                    &self.active_list
+                } else {
+                    &self.completed_list
+                };
 
                 if let Some(todo) = current_list.get_selected_todo(&self.todos) {
                     let todo_id = todo.id;
                     let todo_title = todo.title.clone();
-                    let was_completed = todo.completed;
+                    let was_completed = todo.is_completed();
 
                     match action {
                         ConfirmationAction::Complete(_) => {
-                            self.todos.toggle_todo(&todo_id);
+                            let next_id = self.todos.toggle_todo(&todo_id);
                             self.save_todos()?;
 
                             if was_completed {
                                 self.toast_manager.warning(format!("Reopened: {}", todo_title));
                             } else {
-                                self.toast_manager.success(format!("Completed: {}", todo_title));
+                                match next_id.and_then(|next_id| self.todos.get_todo_by_id(&next_id)) {
+                                    Some(next) => {
+                                        let due = next.due_at
+                                            .map(|due_at| format!(", next due {}", format_due_date(&due_at, &self.config.ui.date_format)))
+                                            .unwrap_or_default();
+                                        self.toast_manager.success_with_undo(format!("Completed: {}{}", todo_title, due));
+                                    }
+                                    None => self.toast_manager.success_with_undo(format!("Completed: {}", todo_title)),
+                                }
+
+                                let open_subtasks = self.todos.get_descendant_ids(&todo_id)
+                                    .iter()
+                                    .filter(|id| self.todos.get_todo_by_id(id).is_some_and(|todo| !todo.is_completed()))
+                                    .count();
+                                if open_subtasks > 0 {
+                                    self.confirmation_modal.open(ConfirmationAction::CompleteSubtasks(todo_id, todo_title, open_subtasks));
+                                }
                             }
                         }
                         ConfirmationAction::Delete(_) => {
                             self.todos.remove_todo(&todo_id);
                             self.save_todos()?;
-                            self.toast_manager.error(format!("Deleted: {}", todo_title));
+                            self.toast_manager.error_with_undo(format!("Deleted: {}", todo_title));
                         }
                         _ => {}
                     }
 
-                    self.active_list.validate_selection(&self.todos);
-                    self.completed_list.validate_selection(&self.todos);
+                    self.refresh_tag_filter();
+                    self.validate_selections();
                 }
             }
         }
@@ -384,32 +841,49 @@ impl App {
                 }
             }
             AppEvent::CtrlEnter | AppEvent::Save => {
-                self.save_todo_from_modal()?;
-                self.add_todo_modal.close();
-                self.editing_todo_id = None;
+                if self.save_todo_from_modal()? {
+                    self.add_todo_modal.close();
+                    self.editing_todo_id = None;
+                }
             }
             AppEvent::Enter => {
+                self.add_todo_modal.handle_enter();
             }
             AppEvent::Tab => {
-                if self.add_todo_modal.is_in_title_mode() {
-                    self.add_todo_modal.switch_to_description();
-                } else {
-                    self.add_todo_modal.switch_to_title();
-                }
+                self.add_todo_modal.next_field();
             }
             AppEvent::BackTab => {
-                if self.add_todo_modal.is_in_title_mode() {
-                    self.add_todo_modal.switch_to_description();
-                } else {
-                    self.add_todo_modal.switch_to_title();
-                }
+                self.add_todo_modal.previous_field();
             }
+            AppEvent::Char(c) => self.add_todo_modal.handle_char(c),
+            AppEvent::Backspace => self.add_todo_modal.handle_backspace(),
+            AppEvent::Left => self.add_todo_modal.move_cursor_left(),
+            AppEvent::Right => self.add_todo_modal.move_cursor_right(),
+            AppEvent::Up => self.add_todo_modal.move_cursor_up(),
+            AppEvent::Down => self.add_todo_modal.move_cursor_down(),
             _ => {}
         }
         Ok(())
     }
 
-    pub(super) fn save_todo_from_modal(&mut self) -> Result<()> {
+    pub(super) fn save_todo_from_modal(&mut self) -> Result<bool> {
+        let due_at = match parse_due_date(self.add_todo_modal.get_due()) {
+            Ok(due_at) => due_at,
+            Err(e) => {
+                self.toast_manager.error(e.to_string());
+                return Ok(false);
+            }
+        };
+
+        let recurrence = match Recurrence::parse(self.add_todo_modal.get_repeat()) {
+            Ok(recurrence) => recurrence,
+            Err(e) => {
+                self.toast_manager.error(e.to_string());
+                return Ok(false);
+            }
+        };
+
+        let tags = parse_tags(self.add_todo_modal.get_tags());
         let title = self.add_todo_modal.get_title();
         if !title.trim().is_empty() {
             let description = self.add_todo_modal.get_description();
@@ -419,12 +893,31 @@ impl App {
                 if let Some(todo) = self.todos.get_todo_by_id_mut(&editing_id) {
                     todo.title = title.to_string();
                     todo.description = description;
+                    todo.due_at = due_at;
+                    todo.tags = tags;
+                    todo.recurrence = recurrence;
                     self.save_todos()?;
                 }
             } else {
-                self.todos.add_todo(title.to_string(), description);
+                let id = match self.adding_child_of {
+                    Some(parent_id) => {
+                        self.todos.set_expanded(&parent_id, true);
+                        self.todos.add_child_todo(&parent_id, title.to_string(), description)
+                    }
+                    None => self.todos.add_todo(title.to_string(), description),
+                };
+                if let Some(todo) = self.todos.get_todo_by_id_mut(&id) {
+                    todo.due_at = due_at;
+                    todo.tags = tags;
+                    todo.recurrence = recurrence;
+                }
+                if self.board_view && self.adding_child_of.is_none() {
+                    let status = self.todos.workflow[self.board_column].clone();
+                    self.todos.set_status(&id, &status);
+                }
                 self.save_todos()?;
             }
+            self.refresh_tag_filter();
 
             if was_editing {
                 self.toast_manager.success("Todo updated successfully!".to_string());
@@ -432,7 +925,7 @@ impl App {
                 self.toast_manager.success("Todo created successfully!".to_string());
             }
         }
-        Ok(())
+        Ok(true)
     }
 
     pub(super) fn handle_help_event(&mut self, event: AppEvent) -> Result<()> {
@@ -497,11 +990,13 @@ impl App {
             AppEvent::Enter | AppEvent::Space => {
                 let old_vim_mode = self.config.ui.vim_mode;
                 let old_compact_mode = self.config.ui.compact_mode;
+                let old_sort_by_priority = self.config.ui.sort_by_priority;
                 self.settings.toggle_selected();
                 self.config.theme = self.settings.get_theme();
                 self.config.ui.date_format = self.settings.get_date_format();
                 self.config.ui.vim_mode = self.settings.get_vim_mode();
                 self.config.ui.compact_mode = self.settings.get_compact_mode();
+                self.config.ui.sort_by_priority = self.settings.get_sort_by_priority();
 
                 if old_vim_mode != self.config.ui.vim_mode {
                     self.add_todo_modal = crate::ui::components::AddTodoModal::new_with_vim_mode(self.config.ui.vim_mode);
@@ -512,6 +1007,15 @@ impl App {
                     self.toast_manager.info(format!("Compact mode: {}", if self.config.ui.compact_mode { "Enabled" } else { "Disabled" }));
                 }
 
+                if old_sort_by_priority != self.config.ui.sort_by_priority {
+                    self.active_list.sort_by_priority = self.config.ui.sort_by_priority;
+                    self.completed_list.sort_by_priority = self.config.ui.sort_by_priority;
+                    for column in self.board_columns.iter_mut() {
+                        column.sort_by_priority = self.config.ui.sort_by_priority;
+                    }
+                    self.toast_manager.info(format!("Sort by priority: {}", if self.config.ui.sort_by_priority { "Enabled" } else { "Disabled" }));
+                }
+
                 self.save_config()?;
             }
             _ => {}
@@ -2,11 +2,12 @@ mod events;
 mod render;
 mod state;
 
-use crate::models::{AppConfig, TodoList};
-use crate::storage::{ConfigStore, JsonStore};
-use crate::ui::components::{AddTodoModal, ConfirmationModal, HelpModal, InputHandler, PreviewModal, SettingsModal, TodoListComponent, ToastManager};
+use crate::models::{AppConfig, TodoList, UndoHistory};
+use crate::storage::{ConfigStore, TodoStore};
+use crate::ui::components::{AddTodoModal, ConfirmationModal, HelpModal, InputHandler, PreviewModal, SettingsModal, TagSidebar, TodoListComponent, ToastManager};
 use anyhow::Result;
 use crossterm::{
+    event::{DisableMouseCapture, EnableMouseCapture},
     execute,
     terminal::{disable_raw_mode, enable_raw_mode, EnterAlternateScreen, LeaveAlternateScreen, Clear, ClearType},
 };
@@ -28,6 +29,10 @@ pub struct App {
     pub active_list: TodoListComponent,
     pub completed_list: TodoListComponent,
     pub active_pane: bool,
+    pub board_view: bool,
+    pub board_columns: Vec<TodoListComponent>,
+    pub board_column: usize,
+    pub tag_sidebar: TagSidebar,
     pub input_handler: InputHandler,
     pub settings: SettingsModal,
     pub add_todo_modal: AddTodoModal,
@@ -36,11 +41,16 @@ pub struct App {
     pub preview_modal: PreviewModal,
     pub toast_manager: ToastManager,
     pub should_quit: bool,
-    json_store: JsonStore,
+    history: UndoHistory,
+    saved_todos: TodoList,
+    store: Box<dyn TodoStore>,
     config_store: ConfigStore,
     editing_todo_id: Option<uuid::Uuid>,
+    adding_child_of: Option<uuid::Uuid>,
     vim_prefix: Option<char>,
     zoomed_pane: Option<bool>,
+    drag_source: Option<uuid::Uuid>,
+    drag_moved: bool,
 }
 
 impl App {
@@ -49,23 +59,31 @@ impl App {
     }
 
     pub fn new_with_options(force_global: bool, custom_path: Option<String>) -> Result<Self> {
-        let (json_store, todos) = Self::initialize_storage(force_global, custom_path)?;
+        let (store, todos) = Self::initialize_storage(force_global, custom_path)?;
         let config_store = ConfigStore::new(ConfigStore::get_default_path()?);
         let config = config_store.load()?;
 
         let mut active_list = TodoListComponent::new();
         active_list.show_completed = false;
+        active_list.sort_by_priority = config.ui.sort_by_priority;
         let mut completed_list = TodoListComponent::new();
         completed_list.show_completed = true;
+        completed_list.sort_by_priority = config.ui.sort_by_priority;
 
         let vim_mode = config.ui.vim_mode;
-        Ok(Self {
+        let board_view = config.ui.board_view;
+        let mut app = Self {
+            saved_todos: todos.clone(),
             todos,
             config,
             mode: AppMode::Normal,
             active_list,
             completed_list,
             active_pane: true,
+            board_view,
+            board_columns: Vec::new(),
+            board_column: 0,
+            tag_sidebar: TagSidebar::new(),
             input_handler: InputHandler::new(vim_mode),
             settings: SettingsModal::new(),
             add_todo_modal: AddTodoModal::new_with_vim_mode(vim_mode),
@@ -74,29 +92,37 @@ impl App {
             preview_modal: PreviewModal::new(),
             toast_manager: ToastManager::new(),
             should_quit: false,
-            json_store,
+            history: UndoHistory::new(),
+            store,
             config_store,
             editing_todo_id: None,
+            adding_child_of: None,
             vim_prefix: None,
             zoomed_pane: None,
-        })
+            drag_source: None,
+            drag_moved: false,
+        };
+        app.sync_board_columns();
+        Ok(app)
     }
 
     pub fn run(&mut self) -> Result<()> {
         enable_raw_mode()?;
         let mut stdout = stdout();
         execute!(stdout, EnterAlternateScreen)?;
+        if self.config.ui.mouse_capture {
+            execute!(stdout, EnableMouseCapture)?;
+        }
         let backend = CrosstermBackend::new(stdout);
         let mut terminal = Terminal::new(backend)?;
 
         let result = self.run_app(&mut terminal);
 
         disable_raw_mode()?;
-        execute!(
-            terminal.backend_mut(),
-            LeaveAlternateScreen,
-            Clear(ClearType::All)
-        )?;
+        if self.config.ui.mouse_capture {
+            execute!(terminal.backend_mut(), DisableMouseCapture)?;
+        }
+        execute!(terminal.backend_mut(), LeaveAlternateScreen, Clear(ClearType::All))?;
         terminal.show_cursor()?;
 
         result
@@ -107,13 +133,21 @@ impl App {
 
         let mut last_render = std::time::Instant::now();
         let render_interval = std::time::Duration::from_millis(100);
+        let mut last_file_check = std::time::Instant::now();
+        let file_check_interval = std::time::Duration::from_secs(1);
 
         while !self.should_quit {
             let timeout = std::time::Duration::from_millis(50);
-            let needs_periodic_render = self.toast_manager.has_active_toasts();
+            let needs_periodic_render = self.toast_manager.has_active_toasts() || self.todos.get_running_timer().is_some();
 
             if crossterm::event::poll(timeout)? {
-                if let crossterm::event::Event::Key(key) = crossterm::event::read()? {
+                let event = crossterm::event::read()?;
+                if let crossterm::event::Event::Mouse(mouse) = event {
+                    if mouse.kind != crossterm::event::MouseEventKind::Moved {
+                        self.handle_mouse_event(mouse)?;
+                        terminal.draw(|frame| self.draw(frame))?;
+                    }
+                } else if let crossterm::event::Event::Key(key) = event {
                     if key.kind != crossterm::event::KeyEventKind::Press {
                         continue;
                     }
@@ -140,9 +174,10 @@ impl App {
                                         self.save_todo_from_modal()?;
                                     }
                                     crate::ui::components::input::VimCommand::SaveAndClose => {
-                                        self.save_todo_from_modal()?;
-                                        self.add_todo_modal.close();
-                                        self.editing_todo_id = None;
+                                        if self.save_todo_from_modal()? {
+                                            self.add_todo_modal.close();
+                                            self.editing_todo_id = None;
+                                        }
                                     }
                                     crate::ui::components::input::VimCommand::Quit => {
                                         if self.add_todo_modal.has_unsaved_changes() {
@@ -166,6 +201,13 @@ impl App {
                     }
                 }
             } else {
+                if last_file_check.elapsed() >= file_check_interval {
+                    last_file_check = std::time::Instant::now();
+                    if self.drag_source.is_none() && self.reload_if_changed()? {
+                        terminal.draw(|frame| self.draw(frame))?;
+                    }
+                }
+
                 if needs_periodic_render && last_render.elapsed() >= render_interval {
                     terminal.draw(|frame| self.draw(frame))?;
                     last_render = std::time::Instant::now();
@@ -1,8 +1,9 @@
+use crate::models::format_duration;
 use crate::ui::{
     styling::{VimIndicator, ThemeColors, ThemeStyles},
 };
 use ratatui::{
-    layout::{Alignment, Constraint, Direction, Layout},
+    layout::{Alignment, Constraint, Direction, Layout, Rect},
     widgets::Paragraph,
     Frame,
 };
@@ -19,27 +20,37 @@ impl App {
             .constraints([Constraint::Length(1), Constraint::Min(0), Constraint::Length(1), Constraint::Length(1)])
             .split(frame.area());
 
-        match self.zoomed_pane {
-            Some(true) => {
-                let mut active_list = self.active_list.clone();
-                active_list.render(frame, chunks[1], &self.todos, &styles, true, self.config.ui.compact_mode, &self.config.ui.date_format);
-            }
-            Some(false) => {
-                let mut completed_list = self.completed_list.clone();
-                completed_list.render(frame, chunks[1], &self.todos, &styles, true, self.config.ui.compact_mode, &self.config.ui.date_format);
-            }
-            None => {
-                let split_ratio = self.config.ui.split_ratio;
-                let main_chunks = Layout::default()
-                    .direction(Direction::Horizontal)
-                    .constraints([Constraint::Percentage(split_ratio), Constraint::Percentage(100 - split_ratio)])
-                    .split(chunks[1]);
+        let list_area = if self.tag_sidebar.visible {
+            let sidebar_chunks = Layout::default()
+                .direction(Direction::Horizontal)
+                .constraints([Constraint::Length(24), Constraint::Min(0)])
+                .split(chunks[1]);
+            self.tag_sidebar.render(frame, sidebar_chunks[0], &self.todos, &styles);
+            sidebar_chunks[1]
+        } else {
+            chunks[1]
+        };
+        let lists_focused = !self.tag_sidebar.focused;
 
-                let mut active_list = self.active_list.clone();
-                let mut completed_list = self.completed_list.clone();
-                active_list.render(frame, main_chunks[0], &self.todos, &styles, self.active_pane, self.config.ui.compact_mode, &self.config.ui.date_format);
-                completed_list.render(frame, main_chunks[1], &self.todos, &styles, !self.active_pane, self.config.ui.compact_mode, &self.config.ui.date_format);
+        if self.board_view {
+            self.active_list.area = Rect::default();
+            self.completed_list.area = Rect::default();
+
+            let column_count = self.board_columns.len().max(1) as u32;
+            let column_chunks = Layout::default()
+                .direction(Direction::Horizontal)
+                .constraints(vec![Constraint::Ratio(1, column_count); column_count as usize])
+                .split(list_area);
+
+            for (index, column) in self.board_columns.iter_mut().enumerate() {
+                let focused = lists_focused && index == self.board_column;
+                column.render(frame, column_chunks[index], &self.todos, &styles, focused, self.config.ui.compact_mode, &self.config.ui.date_format);
+            }
+        } else {
+            for column in self.board_columns.iter_mut() {
+                column.area = Rect::default();
             }
+            self.draw_lists(frame, list_area, &styles, lists_focused);
         }
 
         self.add_todo_modal.render(frame, frame.area(), &styles, &colors);
@@ -50,13 +61,13 @@ impl App {
             if self.config.ui.vim_mode {
                 "Title: Normal typing | Description: Vim mode (i Insert, hjkl Navigate, :w Save, :x Save&Close, :q Quit) | Tab Switch | Ctrl+Enter/Ctrl+S Save"
             } else {
-                "Tab/Enter Switch fields | Ctrl+Enter/Ctrl+S Save | Esc Cancel | Arrow keys Navigate"
+                "Tab/Shift+Tab Switch fields | Ctrl+Enter/Ctrl+S Save | Esc Cancel | Arrow keys Navigate"
             }
         } else {
             if self.config.ui.vim_mode {
-                "+ Add  r Edit  Space Toggle  e Expand  d Delete  Tab/hl Switch  jk Nav  t Theme  s Settings  ? Help  q Quit"
+                "+ Add  r Edit  Space Toggle  HL State  e Expand  d Delete  [] Priority  # Tags  b Board  u Undo  Tab/hl Switch  jk Nav  t Theme  s Settings  ? Help  q Quit"
             } else {
-                "+ Add  r Edit  Space Toggle  e Expand  d Delete  Tab/←→ Switch  ↑↓ Nav  t Theme  s Settings  ? Help  q Quit"
+                "+ Add  r Edit  Space Toggle  HL State  e Expand  d Delete  [] Priority  # Tags  b Board  u Undo  Tab/←→ Switch  ↑↓ Nav  t Theme  s Settings  ? Help  q Quit"
             }
         };
 
@@ -68,8 +79,11 @@ impl App {
         let active_count = self.todos.get_active_todos().len();
         let completed_count = self.todos.get_completed_todos().len();
         let vim_status = if self.config.ui.vim_mode { "  Vim: On" } else { "" };
-        let status_text = format!("TermTask {}  Active: {}  Completed: {}  Theme: {}{}",
-                                 env!("CARGO_PKG_VERSION"), active_count, completed_count, self.config.theme.name(), vim_status);
+        let timer_status = self.todos.get_running_timer()
+            .and_then(|todo| todo.running_entry().map(|entry| format!("  ⏱ {} {}", todo.title, format_duration(&entry.duration()))))
+            .unwrap_or_default();
+        let status_text = format!("TermTask {}  Active: {}  Completed: {}  Theme: {}{}{}",
+                                 env!("CARGO_PKG_VERSION"), active_count, completed_count, self.config.theme.name(), vim_status, timer_status);
 
         let vim_mode = if self.config.ui.vim_mode && self.input_handler.is_active() {
             self.input_handler.get_vim_mode_display()
@@ -99,4 +113,26 @@ impl App {
         self.confirmation_modal.render(frame, frame.area(), &styles, &colors);
     }
 
-}
\ No newline at end of file
+    fn draw_lists(&mut self, frame: &mut Frame, list_area: Rect, styles: &ThemeStyles, lists_focused: bool) {
+        match self.zoomed_pane {
+            Some(true) => {
+                self.active_list.render(frame, list_area, &self.todos, styles, lists_focused, self.config.ui.compact_mode, &self.config.ui.date_format);
+                self.completed_list.area = Rect::default();
+            }
+            Some(false) => {
+                self.completed_list.render(frame, list_area, &self.todos, styles, lists_focused, self.config.ui.compact_mode, &self.config.ui.date_format);
+                self.active_list.area = Rect::default();
+            }
+            None => {
+                let split_ratio = self.config.ui.split_ratio;
+                let main_chunks = Layout::default()
+                    .direction(Direction::Horizontal)
+                    .constraints([Constraint::Percentage(split_ratio), Constraint::Percentage(100 - split_ratio)])
+                    .split(list_area);
+
+                self.active_list.render(frame, main_chunks[0], &self.todos, styles, lists_focused && self.active_pane, self.config.ui.compact_mode, &self.config.ui.date_format);
+                self.completed_list.render(frame, main_chunks[1], &self.todos, styles, lists_focused && !self.active_pane, self.config.ui.compact_mode, &self.config.ui.date_format);
+            }
+        }
+    }
+}
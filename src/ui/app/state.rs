@@ -1,18 +1,18 @@
 use super::App;
 use crate::git::GitRepository;
-use crate::models::TodoList;
+use crate::models::{describe_change, summarize_changes, TodoList};
 use crate::prompt::{ProjectInitializer, TodoStorageChoice};
-use crate::storage::JsonStore;
+use crate::storage::{open_store, JsonStore, SaveOutcome, TodoStore};
 use anyhow::Result;
 use std::io::{self, Write};
 
 impl App {
-    pub(super) fn initialize_storage(force_global: bool, custom_path: Option<String>) -> Result<(JsonStore, TodoList)> {
+    pub(super) fn initialize_storage(force_global: bool, custom_path: Option<String>) -> Result<(Box<dyn TodoStore>, TodoList)> {
         if let Some(path) = custom_path {
             let path_buf = std::path::PathBuf::from(&path);
             if !path_buf.exists() {
                 if Self::prompt_create_custom_file(&path)? {
-                    let store = JsonStore::new(path_buf);
+                    let store = open_store(path_buf)?;
                     let todos = TodoList::new();
                     store.save(&todos)?;
                     return Ok((store, todos));
@@ -20,48 +20,31 @@ impl App {
                     std::process::exit(0);
                 }
             }
-            let store = JsonStore::new(path_buf);
+            let store = open_store(path_buf)?;
             let todos = store.load()?;
             return Ok((store, todos));
         }
 
-        if force_global {
-            let store = JsonStore::new(JsonStore::get_default_path()?);
-            let todos = store.load()?;
-            return Ok((store, todos));
-        }
-
-        if let Some(repo) = GitRepository::find_repository() {
+        let todo_path = if force_global {
+            JsonStore::get_default_path()?
+        } else if let Some(repo) = GitRepository::find_repository() {
             if let Some(existing_todo_path) = repo.has_todo_file() {
-                let store = JsonStore::new(existing_todo_path);
-                let todos = store.load()?;
-                return Ok((store, todos));
-            }
-
-            if let Some(choice) = ProjectInitializer::prompt_for_initialization(&repo) {
-                match choice {
-                    TodoStorageChoice::Global => {
-                        let store = JsonStore::new(JsonStore::get_default_path()?);
-                        let todos = store.load()?;
-                        Ok((store, todos))
-                    }
-                    TodoStorageChoice::Project | TodoStorageChoice::Personal => {
-                        let todo_path = ProjectInitializer::create_todo_file(&repo, choice)?;
-                        let store = JsonStore::new(todo_path);
-                        let todos = store.load()?;
-                        Ok((store, todos))
+                existing_todo_path
+            } else {
+                match ProjectInitializer::prompt_for_initialization(&repo) {
+                    Some(choice @ (TodoStorageChoice::Project | TodoStorageChoice::Personal)) => {
+                        ProjectInitializer::create_todo_file(&repo, choice)?
                     }
+                    Some(TodoStorageChoice::Global) | None => JsonStore::get_default_path()?,
                 }
-            } else {
-                let store = JsonStore::new(JsonStore::get_default_path()?);
-                let todos = store.load()?;
-                Ok((store, todos))
             }
         } else {
-            let store = JsonStore::new(JsonStore::get_default_path()?);
-            let todos = store.load()?;
-            Ok((store, todos))
-        }
+            JsonStore::get_default_path()?
+        };
+
+        let store = open_store(todo_path)?;
+        let todos = store.load()?;
+        Ok((store, todos))
     }
 
     fn prompt_create_custom_file(path: &str) -> Result<bool> {
@@ -78,8 +61,124 @@ impl App {
         Ok(input == "y" || input == "yes")
     }
 
-    pub fn save_todos(&self) -> Result<()> {
-        self.json_store.save(&self.todos)
+    pub fn save_todos(&mut self) -> Result<()> {
+        self.todos.stamp_changes(&self.saved_todos);
+        let outcome = self.store.save(&self.todos)?;
+        let merged = self.apply_save_outcome(outcome);
+        let before = std::mem::replace(&mut self.saved_todos, self.todos.clone());
+        if !merged {
+            self.history.record(before);
+        }
+        Ok(())
+    }
+
+    pub(super) fn restore_todos(&mut self, mut todos: TodoList) -> Result<()> {
+        for item in todos.items.iter_mut() {
+            item.expanded = self.todos.get_todo_by_id(&item.id).is_some_and(|current| current.expanded);
+        }
+
+        todos.stamp_changes(&self.todos);
+        let outcome = self.store.save(&todos)?;
+        self.todos = todos;
+        self.apply_save_outcome(outcome);
+        self.saved_todos = self.todos.clone();
+        self.sync_board_columns();
+        self.refresh_tag_filter();
+        self.validate_selections();
+        Ok(())
+    }
+
+    fn apply_save_outcome(&mut self, outcome: SaveOutcome) -> bool {
+        let SaveOutcome::Merged { list, conflicts } = outcome else { return false };
+
+        self.todos = list;
+        self.history.clear();
+        self.sync_board_columns();
+        self.refresh_tag_filter();
+        self.validate_selections();
+
+        if conflicts > 0 {
+            self.toast_manager.warning(format!(
+                "Merged external changes to the todo file ({} conflicting edit{} resolved by the latest change)",
+                conflicts,
+                if conflicts == 1 { "" } else { "s" }
+            ));
+        } else {
+            self.toast_manager.info("Merged external changes to the todo file".to_string());
+        }
+        true
+    }
+
+    pub(super) fn reload_if_changed(&mut self) -> Result<bool> {
+        if !self.store.changed_externally().unwrap_or(false) {
+            return Ok(false);
+        }
+
+        let mut todos = match self.store.load() {
+            Ok(todos) => todos,
+            Err(e) => {
+                self.toast_manager.error(format!("Could not reload todo file: {}", e));
+                return Ok(true);
+            }
+        };
+
+        for item in todos.items.iter_mut() {
+            item.expanded = self.todos.get_todo_by_id(&item.id).is_some_and(|current| current.expanded);
+        }
+
+        let summary = summarize_changes(&self.todos, &todos);
+        let selected_active = self.active_list.get_selected_todo(&self.todos).map(|todo| todo.id);
+        let selected_completed = self.completed_list.get_selected_todo(&self.todos).map(|todo| todo.id);
+        let selected_columns: Vec<_> = self.board_columns.iter()
+            .map(|column| column.get_selected_todo(&self.todos).map(|todo| todo.id))
+            .collect();
+
+        self.todos = todos;
+        self.saved_todos = self.todos.clone();
+        self.history.clear();
+
+        self.sync_board_columns();
+        self.refresh_tag_filter();
+        self.validate_selections();
+
+        if let Some(id) = selected_active {
+            self.active_list.select_by_id(&self.todos, &id);
+        }
+        if let Some(id) = selected_completed {
+            self.completed_list.select_by_id(&self.todos, &id);
+        }
+        for (column, selected) in self.board_columns.iter_mut().zip(selected_columns) {
+            if let Some(id) = selected {
+                column.select_by_id(&self.todos, &id);
+            }
+        }
+
+        self.toast_manager.info(format!("{} by external change", summary));
+        Ok(true)
+    }
+
+    pub(super) fn undo(&mut self) -> Result<()> {
+        match self.history.undo(self.todos.clone()) {
+            Some(previous) => {
+                let change = describe_change(&self.todos, &previous);
+                self.restore_todos(previous)?;
+                self.toast_manager.info(format!("Undo: {}", change));
+            }
+            None => self.toast_manager.info("Nothing to undo".to_string()),
+        }
+        Ok(())
+    }
+
+    pub(super) fn redo(&mut self) -> Result<()> {
+        match self.history.redo(self.todos.clone()) {
+            Some(next) => {
+                let change = describe_change(&self.todos, &next);
+                self.restore_todos(next)?;
+                self.toast_manager.info(format!("Redo: {}", change));
+            }
+            None => self.toast_manager.info("Nothing to redo".to_string()),
+        }
+        Ok(())
     }
 
     pub fn save_config(&self) -> Result<()> {
@@ -87,20 +186,17 @@ impl App {
     }
 
     pub fn get_storage_context_display(&self) -> String {
-        let store_path = self.json_store.get_file_path();
+        let store_path = self.store.get_file_path();
 
-        if let Some(home_dir) = dirs::home_dir() {
-            let global_path = home_dir.join(".local/share/termtask/todos.json");
-            if *store_path == global_path {
-                return "Global".to_string();
-            }
+        if JsonStore::get_default_path().is_ok_and(|global_path| store_path == global_path) {
+            return "Global".to_string();
         }
 
         if let Some(repo) = GitRepository::find_repository() {
             let project_todo = repo.root.join("todo.json");
             let personal_todo = repo.root.join(".todo.json");
 
-            if *store_path == project_todo {
+            if store_path == project_todo {
                 let project_name = repo.root
                     .file_name()
                     .and_then(|name| name.to_str())
@@ -108,7 +204,7 @@ impl App {
                 return format!("Project: {}", project_name);
             }
 
-            if *store_path == personal_todo {
+            if store_path == personal_todo {
                 let project_name = repo.root
                     .file_name()
                     .and_then(|name| name.to_str())
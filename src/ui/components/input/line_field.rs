@@ -0,0 +1,96 @@
+use crate::ui::styling::{ThemeStyles, ThemeColors};
+use ratatui::{
+    layout::Rect,
+    style::Style,
+    widgets::{Block, Borders, Paragraph},
+    Frame,
+};
+
+#[derive(Debug, Clone, Default)]
+pub struct LineField {
+    pub value: String,
+    pub cursor: usize,
+    label: &'static str,
+    placeholder: &'static str,
+}
+
+impl LineField {
+    pub fn new(label: &'static str, placeholder: &'static str) -> Self {
+        Self {
+            value: String::new(),
+            cursor: 0,
+            label,
+            placeholder,
+        }
+    }
+
+    pub fn set(&mut self, value: &str) {
+        self.value = value.to_string();
+        self.cursor = self.value.len();
+    }
+
+    pub fn clear(&mut self) {
+        self.value.clear();
+        self.cursor = 0;
+    }
+
+    pub fn insert(&mut self, c: char) {
+        self.value.insert(self.cursor, c);
+        self.cursor += c.len_utf8();
+    }
+
+    pub fn backspace(&mut self) {
+        if let Some(c) = self.value[..self.cursor].chars().next_back() {
+            self.cursor -= c.len_utf8();
+            self.value.remove(self.cursor);
+        }
+    }
+
+    pub fn move_left(&mut self) {
+        if let Some(c) = self.value[..self.cursor].chars().next_back() {
+            self.cursor -= c.len_utf8();
+        }
+    }
+
+    pub fn move_right(&mut self) {
+        if let Some(c) = self.value[self.cursor..].chars().next() {
+            self.cursor += c.len_utf8();
+        }
+    }
+
+    pub fn display_with_cursor(&self, cursor: char) -> String {
+        let mut display = self.value.clone();
+        display.insert(self.cursor, cursor);
+        display
+    }
+
+    pub fn render(&self, frame: &mut Frame, area: Rect, focused: bool, cursor: char, styles: &ThemeStyles, colors: &ThemeColors) {
+        let text = if focused {
+            format!("> {}", self.display_with_cursor(cursor))
+        } else if self.value.is_empty() {
+            format!("  {}", self.placeholder)
+        } else {
+            format!("> {}", self.value)
+        };
+
+        let block = Block::default()
+            .title(format!(" {} ", self.label))
+            .borders(Borders::ALL)
+            .border_style(if focused {
+                Style::default().fg(colors.modal_border)
+            } else {
+                Style::default().fg(colors.border)
+            })
+            .style(Style::default().fg(colors.foreground).bg(colors.modal_bg));
+
+        let paragraph = Paragraph::new(text)
+            .block(block)
+            .style(if self.value.is_empty() && !focused {
+                styles.muted
+            } else {
+                Style::default().fg(colors.foreground).bg(colors.modal_bg)
+            });
+
+        frame.render_widget(paragraph, area);
+    }
+}
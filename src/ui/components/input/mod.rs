@@ -1,6 +1,8 @@
+pub mod line_field;
 pub mod normal;
 pub mod vim;
 
+pub use line_field::LineField;
 pub use normal::NormalInput;
 pub use vim::{VimInput, InputMode as VimInputMode, VimCommand};
 
@@ -36,10 +38,10 @@ impl InputHandler {
         }
     }
 
-    pub fn open_with_data(&mut self, title: &str, description: Option<&str>) {
+    pub fn open_with_data(&mut self, title: &str, description: Option<&str>, due: &str, tags: &str, repeat: &str) {
         match self {
-            Self::Normal(input) => input.open_with_data(title, description),
-            Self::Vim(input) => input.open_with_data(title, description),
+            Self::Normal(input) => input.open_with_data(title, description, due, tags, repeat),
+            Self::Vim(input) => input.open_with_data(title, description, due, tags, repeat),
         }
     }
 
@@ -64,6 +66,27 @@ impl InputHandler {
         }
     }
 
+    pub fn get_due(&self) -> &str {
+        match self {
+            Self::Normal(input) => input.get_due(),
+            Self::Vim(input) => input.get_due(),
+        }
+    }
+
+    pub fn get_tags(&self) -> &str {
+        match self {
+            Self::Normal(input) => input.get_tags(),
+            Self::Vim(input) => input.get_tags(),
+        }
+    }
+
+    pub fn get_repeat(&self) -> &str {
+        match self {
+            Self::Normal(input) => input.get_repeat(),
+            Self::Vim(input) => input.get_repeat(),
+        }
+    }
+
     pub fn render(&mut self, frame: &mut Frame, area: Rect, styles: &ThemeStyles, colors: &ThemeColors) {
         match self {
             Self::Normal(input) => input.render(frame, area, styles, colors),
@@ -105,31 +128,66 @@ impl InputHandler {
             Self::Normal(_) => true,
             Self::Vim(input) => {
                 match input.mode {
-                    VimInputMode::Title => true,
+                    VimInputMode::Title | VimInputMode::Due | VimInputMode::Tags | VimInputMode::Repeat => true,
                     VimInputMode::Description => input.is_edtui_in_normal_mode(),
                 }
             }
         }
     }
 
-    pub fn switch_to_description(&mut self) {
-        if let Self::Vim(input) = self {
-            input.switch_to_description();
+    pub fn handle_char(&mut self, c: char) {
+        if let Self::Normal(input) = self {
+            input.handle_char(c);
         }
     }
 
-    pub fn switch_to_title(&mut self) {
-        if let Self::Vim(input) = self {
-            input.switch_to_title();
+    pub fn handle_backspace(&mut self) {
+        if let Self::Normal(input) = self {
+            input.handle_backspace();
         }
     }
 
-    pub fn is_in_title_mode(&self) -> bool {
-        match self {
-            Self::Normal(_) => true,
-            Self::Vim(input) => input.is_in_title_mode(),
+    pub fn handle_enter(&mut self) {
+        if let Self::Normal(input) = self {
+            input.handle_enter();
+        }
+    }
+
+    pub fn move_cursor_left(&mut self) {
+        if let Self::Normal(input) = self {
+            input.move_cursor_left();
+        }
+    }
+
+    pub fn move_cursor_right(&mut self) {
+        if let Self::Normal(input) = self {
+            input.move_cursor_right();
+        }
+    }
+
+    pub fn move_cursor_up(&mut self) {
+        if let Self::Normal(input) = self {
+            input.move_cursor_up();
+        }
+    }
+
+    pub fn move_cursor_down(&mut self) {
+        if let Self::Normal(input) = self {
+            input.move_cursor_down();
         }
     }
 
+    pub fn next_field(&mut self) {
+        match self {
+            Self::Normal(input) => input.next_field(),
+            Self::Vim(input) => input.next_field(),
+        }
+    }
 
+    pub fn previous_field(&mut self) {
+        match self {
+            Self::Normal(input) => input.previous_field(),
+            Self::Vim(input) => input.previous_field(),
+        }
+    }
 }
\ No newline at end of file
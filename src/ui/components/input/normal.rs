@@ -1,3 +1,4 @@
+use super::LineField;
 use crate::ui::styling::{ThemeStyles, ThemeColors};
 use ratatui::{
     layout::{Alignment, Constraint, Direction, Layout, Rect},
@@ -10,6 +11,9 @@ use ratatui::{
 pub enum InputMode {
     Title,
     Description,
+    Due,
+    Tags,
+    Repeat,
 }
 
 pub struct NormalInput {
@@ -20,6 +24,9 @@ pub struct NormalInput {
     pub mode: InputMode,
     pub current_line: usize,
     pub cursor_col: usize,
+    pub due: LineField,
+    pub tags: LineField,
+    pub repeat: LineField,
     pub is_editing: bool,
 }
 
@@ -33,6 +40,9 @@ impl NormalInput {
             mode: InputMode::Title,
             current_line: 0,
             cursor_col: 0,
+            due: LineField::new("Due", "tomorrow, fri, in 3d (optional)"),
+            tags: LineField::new("Tags", "frontend, infra (optional)"),
+            repeat: LineField::new("Repeat", "daily, weekly mon,thu, every 3d (optional)"),
             is_editing: false,
         }
     }
@@ -45,10 +55,13 @@ impl NormalInput {
         self.mode = InputMode::Title;
         self.current_line = 0;
         self.cursor_col = 0;
+        self.due.clear();
+        self.tags.clear();
+        self.repeat.clear();
         self.is_editing = false;
     }
 
-    pub fn open_with_data(&mut self, title: &str, description: Option<&str>) {
+    pub fn open_with_data(&mut self, title: &str, description: Option<&str>, due: &str, tags: &str, repeat: &str) {
         self.active = true;
         self.title = title.to_string();
         self.cursor_position = title.len();
@@ -65,6 +78,9 @@ impl NormalInput {
         self.mode = InputMode::Title;
         self.current_line = 0;
         self.cursor_col = 0;
+        self.due.set(due);
+        self.tags.set(tags);
+        self.repeat.set(repeat);
         self.is_editing = true;
     }
 
@@ -83,6 +99,9 @@ impl NormalInput {
                 current_line.insert(self.cursor_col, c);
                 self.cursor_col += 1;
             }
+            InputMode::Due => self.due.insert(c),
+            InputMode::Tags => self.tags.insert(c),
+            InputMode::Repeat => self.repeat.insert(c),
         }
     }
 
@@ -107,6 +126,9 @@ impl NormalInput {
                     self.cursor_col = prev_line_len;
                 }
             }
+            InputMode::Due => self.due.backspace(),
+            InputMode::Tags => self.tags.backspace(),
+            InputMode::Repeat => self.repeat.backspace(),
         }
     }
 
@@ -124,6 +146,7 @@ impl NormalInput {
                 self.cursor_col = 0;
                 false
             }
+            InputMode::Due | InputMode::Tags | InputMode::Repeat => false,
         }
     }
 
@@ -142,6 +165,9 @@ impl NormalInput {
                     self.cursor_col = self.description[self.current_line].len();
                 }
             }
+            InputMode::Due => self.due.move_left(),
+            InputMode::Tags => self.tags.move_left(),
+            InputMode::Repeat => self.repeat.move_left(),
         }
     }
 
@@ -160,6 +186,9 @@ impl NormalInput {
                     self.cursor_col = 0;
                 }
             }
+            InputMode::Due => self.due.move_right(),
+            InputMode::Tags => self.tags.move_right(),
+            InputMode::Repeat => self.repeat.move_right(),
         }
     }
 
@@ -177,10 +206,42 @@ impl NormalInput {
         }
     }
 
+    pub fn next_field(&mut self) {
+        self.mode = match self.mode {
+            InputMode::Title => InputMode::Description,
+            InputMode::Description => InputMode::Due,
+            InputMode::Due => InputMode::Tags,
+            InputMode::Tags => InputMode::Repeat,
+            InputMode::Repeat => InputMode::Title,
+        };
+    }
+
+    pub fn previous_field(&mut self) {
+        self.mode = match self.mode {
+            InputMode::Title => InputMode::Repeat,
+            InputMode::Description => InputMode::Title,
+            InputMode::Due => InputMode::Description,
+            InputMode::Tags => InputMode::Due,
+            InputMode::Repeat => InputMode::Tags,
+        };
+    }
+
     pub fn get_title(&self) -> &str {
         &self.title
     }
 
+    pub fn get_due(&self) -> &str {
+        &self.due.value
+    }
+
+    pub fn get_tags(&self) -> &str {
+        &self.tags.value
+    }
+
+    pub fn get_repeat(&self) -> &str {
+        &self.repeat.value
+    }
+
     pub fn get_description(&self) -> Option<String> {
         let desc = self.description.join("\n").trim().to_string();
         if desc.is_empty() {
@@ -215,6 +276,7 @@ impl NormalInput {
             .constraints([
                 Constraint::Length(3),
                 Constraint::Min(5),
+                Constraint::Length(3),
                 Constraint::Length(1),
             ])
             .split(popup_area);
@@ -311,16 +373,27 @@ impl NormalInput {
 
         frame.render_widget(description_paragraph, chunks[1]);
 
+        let detail_chunks = Layout::default()
+            .direction(Direction::Horizontal)
+            .constraints([Constraint::Ratio(1, 3), Constraint::Ratio(1, 3), Constraint::Ratio(1, 3)])
+            .split(chunks[2]);
+
+        self.due.render(frame, detail_chunks[0], matches!(self.mode, InputMode::Due), '_', styles, colors);
+        self.tags.render(frame, detail_chunks[1], matches!(self.mode, InputMode::Tags), '_', styles, colors);
+        self.repeat.render(frame, detail_chunks[2], matches!(self.mode, InputMode::Repeat), '_', styles, colors);
+
         let mode_indicator = if self.is_editing { "[EDIT]" } else { "[INSERT]" };
         let help_text = match self.mode {
             InputMode::Title => format!("{} <C-Enter>/<C-s> Save | <Enter>/<Tab> Next | <Esc> Close", mode_indicator),
-            InputMode::Description => format!("{} <C-Enter>/<C-s> Save | <Enter> Newline | <Tab> Back to Title | <Esc> Close", mode_indicator),
+            InputMode::Description => format!("{} <C-Enter>/<C-s> Save | <Enter> Newline | <Tab> Next | <Esc> Close", mode_indicator),
+            InputMode::Due | InputMode::Tags => format!("{} <C-Enter>/<C-s> Save | <Tab> Next | <Esc> Close", mode_indicator),
+            InputMode::Repeat => format!("{} <C-Enter>/<C-s> Save | <Tab> Back to Title | <Esc> Close", mode_indicator),
         };
 
         let status_line = Paragraph::new(help_text)
             .style(Style::default().fg(colors.muted).bg(colors.modal_bg))
             .alignment(Alignment::Left);
 
-        frame.render_widget(status_line, chunks[2]);
+        frame.render_widget(status_line, chunks[3]);
     }
 }
\ No newline at end of file
@@ -1,3 +1,4 @@
+use super::LineField;
 use crate::ui::styling::{ThemeStyles, ThemeColors};
 use ratatui::{
     layout::{Alignment, Constraint, Direction, Layout, Rect},
@@ -13,6 +14,9 @@ use crossterm::event::KeyEvent;
 pub enum InputMode {
     Title,
     Description,
+    Due,
+    Tags,
+    Repeat,
 }
 
 #[derive(Debug, Clone, PartialEq)]
@@ -32,6 +36,9 @@ pub struct VimInput {
     pub command_buffer: String,
     pub description_editor: EditorState,
     pub description_event_handler: EditorEventHandler,
+    pub due: LineField,
+    pub tags: LineField,
+    pub repeat: LineField,
 }
 
 impl VimInput {
@@ -45,6 +52,9 @@ impl VimInput {
             command_buffer: String::new(),
             description_editor: EditorState::default(),
             description_event_handler: EditorEventHandler::default(),
+            due: LineField::new("Due", "tomorrow, fri, in 3d (optional)"),
+            tags: LineField::new("Tags", "frontend, infra (optional)"),
+            repeat: LineField::new("Repeat", "daily, weekly mon,thu, every 3d (optional)"),
         }
     }
 
@@ -57,9 +67,12 @@ impl VimInput {
         self.command_buffer.clear();
         self.description_editor = EditorState::default();
         self.description_event_handler = EditorEventHandler::default();
+        self.due.clear();
+        self.tags.clear();
+        self.repeat.clear();
     }
 
-    pub fn open_with_data(&mut self, title: &str, description: Option<&str>) {
+    pub fn open_with_data(&mut self, title: &str, description: Option<&str>, due: &str, tags: &str, repeat: &str) {
         self.active = true;
         self.title = title.to_string();
         self.cursor_position = title.len();
@@ -74,6 +87,9 @@ impl VimInput {
             self.description_editor = EditorState::default();
         }
         self.description_event_handler = EditorEventHandler::default();
+        self.due.set(due);
+        self.tags.set(tags);
+        self.repeat.set(repeat);
     }
 
     pub fn close(&mut self) {
@@ -85,6 +101,7 @@ impl VimInput {
         match self.mode {
             InputMode::Title => true,
             InputMode::Description => true,
+            InputMode::Due | InputMode::Tags | InputMode::Repeat => true,
         }
     }
 
@@ -94,7 +111,7 @@ impl VimInput {
             "COMMAND"
         } else {
             match self.mode {
-                InputMode::Title => "INSERT",
+                InputMode::Title | InputMode::Due | InputMode::Tags | InputMode::Repeat => "INSERT",
                 InputMode::Description => "EDIT",
             }
         }
@@ -176,6 +193,21 @@ impl VimInput {
                 }
                 None
             }
+            InputMode::Due | InputMode::Tags | InputMode::Repeat => {
+                let field = match self.mode {
+                    InputMode::Due => &mut self.due,
+                    InputMode::Tags => &mut self.tags,
+                    _ => &mut self.repeat,
+                };
+                match key.code {
+                    crossterm::event::KeyCode::Char(c) => field.insert(c),
+                    crossterm::event::KeyCode::Backspace => field.backspace(),
+                    crossterm::event::KeyCode::Left => field.move_left(),
+                    crossterm::event::KeyCode::Right => field.move_right(),
+                    _ => {}
+                }
+                None
+            }
         }
     }
 
@@ -184,6 +216,18 @@ impl VimInput {
         &self.title
     }
 
+    pub fn get_due(&self) -> &str {
+        &self.due.value
+    }
+
+    pub fn get_tags(&self) -> &str {
+        &self.tags.value
+    }
+
+    pub fn get_repeat(&self) -> &str {
+        &self.repeat.value
+    }
+
     pub fn get_description(&self) -> Option<String> {
         let text = self.extract_text_from_edtui();
         if text.trim().is_empty() {
@@ -253,16 +297,24 @@ impl VimInput {
         matches!(self.description_editor.mode, EditorMode::Normal)
     }
 
-    pub fn switch_to_description(&mut self) {
-        self.mode = InputMode::Description;
-    }
-
-    pub fn switch_to_title(&mut self) {
-        self.mode = InputMode::Title;
+    pub fn next_field(&mut self) {
+        self.mode = match self.mode {
+            InputMode::Title => InputMode::Description,
+            InputMode::Description => InputMode::Due,
+            InputMode::Due => InputMode::Tags,
+            InputMode::Tags => InputMode::Repeat,
+            InputMode::Repeat => InputMode::Title,
+        };
     }
 
-    pub fn is_in_title_mode(&self) -> bool {
-        matches!(self.mode, InputMode::Title)
+    pub fn previous_field(&mut self) {
+        self.mode = match self.mode {
+            InputMode::Title => InputMode::Repeat,
+            InputMode::Description => InputMode::Title,
+            InputMode::Due => InputMode::Description,
+            InputMode::Tags => InputMode::Due,
+            InputMode::Repeat => InputMode::Tags,
+        };
     }
 
     fn parse_vim_command(&self, cmd: &str) -> Option<VimCommand> {
@@ -309,6 +361,7 @@ impl VimInput {
         let constraints = vec![
             Constraint::Length(3),
             Constraint::Min(5),
+            Constraint::Length(3),
             Constraint::Length(1),
         ];
         let chunks = Layout::default()
@@ -359,6 +412,15 @@ impl VimInput {
             .wrap(true)
             .render(inner_area, frame.buffer_mut());
 
+        let detail_chunks = Layout::default()
+            .direction(Direction::Horizontal)
+            .constraints([Constraint::Ratio(1, 3), Constraint::Ratio(1, 3), Constraint::Ratio(1, 3)])
+            .split(chunks[2]);
+
+        self.due.render(frame, detail_chunks[0], matches!(self.mode, InputMode::Due), '█', styles, colors);
+        self.tags.render(frame, detail_chunks[1], matches!(self.mode, InputMode::Tags), '█', styles, colors);
+        self.repeat.render(frame, detail_chunks[2], matches!(self.mode, InputMode::Repeat), '█', styles, colors);
+
         let mode_indicator = self.get_vim_mode_display();
         let help_text = if !self.command_buffer.is_empty() {
             format!("[{}] {}", mode_indicator, self.command_buffer)
@@ -366,6 +428,7 @@ impl VimInput {
             match &self.mode {
                 InputMode::Title => format!("[{}] <Enter> Next | <C-Enter>/<C-s> Save | <Esc> Close", mode_indicator),
                 InputMode::Description => format!("[{}] Full Vim Editor (:w save, :x save&close, :q quit) | <C-Enter>/<C-s> Save | <Esc> Close", mode_indicator),
+                InputMode::Due | InputMode::Tags | InputMode::Repeat => format!("[{}] <Tab> Next | <C-Enter>/<C-s> Save | <Esc> Close", mode_indicator),
             }
         };
 
@@ -373,6 +436,6 @@ impl VimInput {
             .style(Style::default().fg(colors.muted).bg(colors.modal_bg))
             .alignment(Alignment::Left);
 
-        frame.render_widget(status_line, chunks[2]);
+        frame.render_widget(status_line, chunks[3]);
     }
 }
\ No newline at end of file
@@ -1,9 +1,11 @@
 pub mod input;
 pub mod modals;
+pub mod tag_sidebar;
 pub mod toast;
 pub mod todo_list;
 
 pub use input::InputHandler;
 pub use modals::*;
+pub use tag_sidebar::*;
 pub use toast::*;
 pub use todo_list::*;
\ No newline at end of file
@@ -6,6 +6,9 @@ pub struct AddTodoModal {
     pub input_handler: InputHandler,
     original_title: String,
     original_description: Option<String>,
+    original_due: String,
+    original_tags: String,
+    original_repeat: String,
 }
 
 
@@ -15,6 +18,9 @@ impl AddTodoModal {
             input_handler: InputHandler::new(vim_mode),
             original_title: String::new(),
             original_description: None,
+            original_due: String::new(),
+            original_tags: String::new(),
+            original_repeat: String::new(),
         }
     }
 
@@ -22,12 +28,18 @@ impl AddTodoModal {
         self.input_handler.open();
         self.original_title = String::new();
         self.original_description = None;
+        self.original_due = String::new();
+        self.original_tags = String::new();
+        self.original_repeat = String::new();
     }
 
-    pub fn open_with_data(&mut self, title: &str, description: Option<&str>) {
-        self.input_handler.open_with_data(title, description);
+    pub fn open_with_data(&mut self, title: &str, description: Option<&str>, due: &str, tags: &str, repeat: &str) {
+        self.input_handler.open_with_data(title, description, due, tags, repeat);
         self.original_title = title.to_string();
         self.original_description = description.map(|d| d.to_string());
+        self.original_due = due.to_string();
+        self.original_tags = tags.to_string();
+        self.original_repeat = repeat.to_string();
     }
 
     pub fn close(&mut self) {
@@ -52,6 +64,18 @@ impl AddTodoModal {
         self.input_handler.get_description()
     }
 
+    pub fn get_due(&self) -> &str {
+        self.input_handler.get_due()
+    }
+
+    pub fn get_tags(&self) -> &str {
+        self.input_handler.get_tags()
+    }
+
+    pub fn get_repeat(&self) -> &str {
+        self.input_handler.get_repeat()
+    }
+
 
 
 
@@ -73,7 +97,11 @@ impl AddTodoModal {
         let current_title = self.get_title();
         let current_description = self.get_description();
 
-        if current_title != self.original_title {
+        if current_title != self.original_title
+            || self.get_due().trim() != self.original_due.trim()
+            || self.get_tags().trim() != self.original_tags.trim()
+            || self.get_repeat().trim() != self.original_repeat.trim()
+        {
             return true;
         }
 
@@ -85,15 +113,39 @@ impl AddTodoModal {
         }
     }
 
-    pub fn switch_to_description(&mut self) {
-        self.input_handler.switch_to_description();
+    pub fn handle_char(&mut self, c: char) {
+        self.input_handler.handle_char(c);
+    }
+
+    pub fn handle_backspace(&mut self) {
+        self.input_handler.handle_backspace();
+    }
+
+    pub fn handle_enter(&mut self) {
+        self.input_handler.handle_enter();
+    }
+
+    pub fn move_cursor_left(&mut self) {
+        self.input_handler.move_cursor_left();
+    }
+
+    pub fn move_cursor_right(&mut self) {
+        self.input_handler.move_cursor_right();
+    }
+
+    pub fn move_cursor_up(&mut self) {
+        self.input_handler.move_cursor_up();
+    }
+
+    pub fn move_cursor_down(&mut self) {
+        self.input_handler.move_cursor_down();
     }
 
-    pub fn switch_to_title(&mut self) {
-        self.input_handler.switch_to_title();
+    pub fn next_field(&mut self) {
+        self.input_handler.next_field();
     }
 
-    pub fn is_in_title_mode(&self) -> bool {
-        self.input_handler.is_in_title_mode()
+    pub fn previous_field(&mut self) {
+        self.input_handler.previous_field();
     }
 }
\ No newline at end of file
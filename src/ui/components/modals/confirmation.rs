@@ -1,4 +1,5 @@
 use crate::ui::styling::{ThemeStyles, ThemeColors};
+use uuid::Uuid;
 use ratatui::{
     layout::{Alignment, Constraint, Direction, Layout, Rect},
     style::Style,
@@ -9,24 +10,30 @@ use ratatui::{
 #[derive(Debug, Clone)]
 pub enum ConfirmationAction {
     Complete(String),
+    CompleteSubtasks(Uuid, String, usize),
     Delete(String),
     DiscardUnsavedChanges,
+    QuitWithRunningTimer(String),
 }
 
 impl ConfirmationAction {
     pub fn title(&self) -> &str {
         match self {
             ConfirmationAction::Complete(_) => "Complete Todo",
+            ConfirmationAction::CompleteSubtasks(..) => "Complete Subtasks",
             ConfirmationAction::Delete(_) => "Delete Todo",
             ConfirmationAction::DiscardUnsavedChanges => "Discard Changes",
+            ConfirmationAction::QuitWithRunningTimer(_) => "Timer Running",
         }
     }
 
     pub fn message(&self) -> String {
         match self {
             ConfirmationAction::Complete(title) => format!("Complete todo \"{}\"?", title),
+            ConfirmationAction::CompleteSubtasks(_, title, count) => format!("Also complete {} open subtask(s) of \"{}\"?", count, title),
             ConfirmationAction::Delete(title) => format!("Delete todo \"{}\"?", title),
             ConfirmationAction::DiscardUnsavedChanges => "You have unsaved changes. Discard them?".to_string(),
+            ConfirmationAction::QuitWithRunningTimer(title) => format!("A timer is running on \"{}\". Stop it and quit?", title),
         }
     }
 }
@@ -56,19 +56,29 @@ impl HelpModal {
             KeyBinding { key: "l/→", description: "Go to right pane (completed)", category: "Navigation", vim_only: false, normal_only: false },
             KeyBinding { key: "Enter", description: "Select/Edit item", category: "Navigation", vim_only: false, normal_only: false },
             KeyBinding { key: "+", description: "Add new todo", category: "Todo Management", vim_only: false, normal_only: false },
+            KeyBinding { key: "A", description: "Add subtask under selected todo", category: "Todo Management", vim_only: false, normal_only: false },
+            KeyBinding { key: ">/<", description: "Indent/outdent selected todo", category: "Todo Management", vim_only: false, normal_only: false },
             KeyBinding { key: "r", description: "Edit selected todo", category: "Todo Management", vim_only: false, normal_only: false },
             KeyBinding { key: "p", description: "Preview todo with markdown", category: "Todo Management", vim_only: false, normal_only: false },
             KeyBinding { key: "Space", description: "Toggle todo completion", category: "Todo Management", vim_only: false, normal_only: false },
             KeyBinding { key: "d", description: "Delete selected todo", category: "Todo Management", vim_only: false, normal_only: false },
-            KeyBinding { key: "e", description: "Expand/collapse description", category: "Todo Management", vim_only: false, normal_only: false },
-            KeyBinding { key: "E", description: "Expand all descriptions", category: "View Options", vim_only: false, normal_only: false },
-            KeyBinding { key: "C", description: "Collapse all descriptions", category: "View Options", vim_only: false, normal_only: false },
+            KeyBinding { key: "e", description: "Expand/collapse description and subtasks", category: "Todo Management", vim_only: false, normal_only: false },
+            KeyBinding { key: "K/Alt+↑", description: "Move selected todo up", category: "Todo Management", vim_only: false, normal_only: false },
+            KeyBinding { key: "J/Alt+↓", description: "Move selected todo down", category: "Todo Management", vim_only: false, normal_only: false },
+            KeyBinding { key: "H/L", description: "Move selected todo to previous/next workflow state", category: "Todo Management", vim_only: false, normal_only: false },
+            KeyBinding { key: "T", description: "Start/stop timer on selected todo", category: "Todo Management", vim_only: false, normal_only: false },
+            KeyBinding { key: "]", description: "Raise priority", category: "Todo Management", vim_only: false, normal_only: false },
+            KeyBinding { key: "[", description: "Lower priority", category: "Todo Management", vim_only: false, normal_only: false },
+            KeyBinding { key: "E", description: "Expand all descriptions and subtasks", category: "View Options", vim_only: false, normal_only: false },
+            KeyBinding { key: "C", description: "Collapse all descriptions and subtasks", category: "View Options", vim_only: false, normal_only: false },
             KeyBinding { key: "=/+", description: "Increase split ratio", category: "View Options", vim_only: false, normal_only: false },
             KeyBinding { key: "-", description: "Decrease split ratio", category: "View Options", vim_only: false, normal_only: false },
             KeyBinding { key: "f", description: "Focus/zoom pane", category: "View Options", vim_only: false, normal_only: false },
+            KeyBinding { key: "b", description: "Toggle kanban board view", category: "View Options", vim_only: false, normal_only: false },
+            KeyBinding { key: "#", description: "Toggle tag sidebar (Enter filters by tag)", category: "View Options", vim_only: false, normal_only: false },
             KeyBinding { key: "t", description: "Toggle theme quickly", category: "Settings", vim_only: false, normal_only: false },
             KeyBinding { key: "s", description: "Open settings modal", category: "Settings", vim_only: false, normal_only: false },
-            KeyBinding { key: "Tab/Enter", description: "Switch fields", category: "Modal Controls", vim_only: false, normal_only: true },
+            KeyBinding { key: "Tab/Shift+Tab", description: "Next/previous field (Title, Description, Due, Tags, Repeat)", category: "Modal Controls", vim_only: false, normal_only: true },
             KeyBinding { key: "Ctrl+Enter/Ctrl+S", description: "Save", category: "Modal Controls", vim_only: false, normal_only: true },
             KeyBinding { key: "Esc", description: "Close modal/Cancel", category: "Modal Controls", vim_only: false, normal_only: true },
             KeyBinding { key: "Arrow keys", description: "Navigate", category: "Modal Controls", vim_only: false, normal_only: true },
@@ -87,6 +97,8 @@ impl HelpModal {
             KeyBinding { key: "yy", description: "Yank (copy) line", category: "Modal Controls (Vim)", vim_only: true, normal_only: false },
             KeyBinding { key: "p", description: "Paste after cursor", category: "Modal Controls (Vim)", vim_only: true, normal_only: false },
             KeyBinding { key: "v/V", description: "Visual/Visual line mode", category: "Modal Controls (Vim)", vim_only: true, normal_only: false },
+            KeyBinding { key: "u", description: "Undo last change", category: "Application", vim_only: false, normal_only: false },
+            KeyBinding { key: "Ctrl+R", description: "Redo last undone change", category: "Application", vim_only: false, normal_only: false },
             KeyBinding { key: "?", description: "Show this help", category: "Application", vim_only: false, normal_only: false },
             KeyBinding { key: "q", description: "Quit application", category: "Application", vim_only: false, normal_only: false },
             KeyBinding { key: "Ctrl+C", description: "Force quit", category: "Application", vim_only: false, normal_only: false },
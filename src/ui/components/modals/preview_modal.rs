@@ -11,6 +11,7 @@ pub struct PreviewModal {
     pub active: bool,
     title: String,
     description: String,
+    details: Vec<String>,
     scroll_offset: usize,
 }
 
@@ -20,14 +21,16 @@ impl PreviewModal {
             active: false,
             title: String::new(),
             description: String::new(),
+            details: Vec::new(),
             scroll_offset: 0,
         }
     }
 
-    pub fn open(&mut self, title: &str, description: Option<&str>) {
+    pub fn open(&mut self, title: &str, description: Option<&str>, details: Vec<String>) {
         self.active = true;
         self.title = title.to_string();
         self.description = description.unwrap_or("").to_string();
+        self.details = details;
         self.scroll_offset = 0;
     }
 
@@ -70,6 +73,7 @@ impl PreviewModal {
             .direction(Direction::Vertical)
             .constraints([
                 Constraint::Length(3),
+                Constraint::Length(self.details.len() as u16),
                 Constraint::Min(0),
                 Constraint::Length(1),
             ])
@@ -88,6 +92,20 @@ impl PreviewModal {
 
         frame.render_widget(title_paragraph, chunks[0]);
 
+        if !self.details.is_empty() {
+            let details_block = Block::default()
+                .borders(Borders::LEFT | Borders::RIGHT)
+                .border_style(Style::default().fg(colors.modal_border))
+                .style(Style::default().fg(colors.foreground).bg(colors.modal_bg));
+
+            let details_paragraph = Paragraph::new(self.details.join("\n"))
+                .block(details_block)
+                .style(styles.muted.bg(colors.modal_bg))
+                .alignment(Alignment::Center);
+
+            frame.render_widget(details_paragraph, chunks[1]);
+        }
+
         let content_block = Block::default()
             .borders(Borders::LEFT | Borders::RIGHT | Borders::BOTTOM)
             .border_style(Style::default().fg(colors.modal_border))
@@ -99,7 +117,7 @@ impl PreviewModal {
             ratatui::text::Text::from("(No description)")
         };
 
-        let content_height = chunks[1].height as usize;
+        let content_height = chunks[2].height as usize;
         let total_lines = markdown_text.lines.len();
         let visible_lines = content_height.saturating_sub(2);
 
@@ -119,7 +137,7 @@ impl PreviewModal {
             .style(Style::default().fg(colors.foreground).bg(colors.modal_bg))
             .wrap(Wrap { trim: false });
 
-        frame.render_widget(content_paragraph, chunks[1]);
+        frame.render_widget(content_paragraph, chunks[2]);
 
         let footer_text = if total_lines > visible_lines {
             format!("Press Esc or p to close | [{}/{}] Use ↑/↓ or j/k to scroll",
@@ -133,6 +151,6 @@ impl PreviewModal {
             .style(Style::default().fg(colors.muted).bg(colors.modal_bg))
             .alignment(Alignment::Center);
 
-        frame.render_widget(footer_paragraph, chunks[2]);
+        frame.render_widget(footer_paragraph, chunks[3]);
     }
 }
\ No newline at end of file
@@ -14,6 +14,7 @@ pub enum SettingItem {
     DateFormat(DateFormat),
     VimMode(bool),
     CompactMode(bool),
+    SortByPriority(bool),
 }
 
 impl SettingItem {
@@ -23,6 +24,7 @@ impl SettingItem {
             SettingItem::DateFormat(format) => format!("Date Format: {}", format.name()),
             SettingItem::VimMode(enabled) => format!("Vim Mode: {}", if *enabled { "Enabled" } else { "Disabled" }),
             SettingItem::CompactMode(enabled) => format!("Compact Mode: {}", if *enabled { "Enabled" } else { "Disabled" }),
+            SettingItem::SortByPriority(enabled) => format!("Sort by Priority: {}", if *enabled { "Enabled" } else { "Disabled" }),
         }
     }
 }
@@ -48,6 +50,7 @@ impl SettingsModal {
                 SettingItem::DateFormat(default_date_format.clone()),
                 SettingItem::VimMode(false),
                 SettingItem::CompactMode(false),
+                SettingItem::SortByPriority(true),
             ],
             theme_selector: MultiSelect::new("Select Theme".to_string(), default_theme),
             date_format_selector: MultiSelect::new("Select Date Format".to_string(), default_date_format),
@@ -55,7 +58,7 @@ impl SettingsModal {
         }
     }
 
-    pub fn open(&mut self, current_theme: &ColorTheme, date_format: &DateFormat, vim_mode: bool, compact_mode: bool) {
+    pub fn open(&mut self, current_theme: &ColorTheme, date_format: &DateFormat, vim_mode: bool, compact_mode: bool, sort_by_priority: bool) {
         self.active = true;
         self.selected_index = 0;
         self.multi_select_active = false;
@@ -64,6 +67,7 @@ impl SettingsModal {
             SettingItem::DateFormat(date_format.clone()),
             SettingItem::VimMode(vim_mode),
             SettingItem::CompactMode(compact_mode),
+            SettingItem::SortByPriority(sort_by_priority),
         ];
         self.theme_selector = MultiSelect::new("Select Theme".to_string(), current_theme.clone());
         self.date_format_selector = MultiSelect::new("Select Date Format".to_string(), date_format.clone());
@@ -104,6 +108,9 @@ impl SettingsModal {
             SettingItem::CompactMode(enabled) => {
                 *enabled = !*enabled;
             }
+            SettingItem::SortByPriority(enabled) => {
+                *enabled = !*enabled;
+            }
         }
     }
 
@@ -202,6 +209,15 @@ impl SettingsModal {
         false
     }
 
+    pub fn get_sort_by_priority(&self) -> bool {
+        for setting in &self.settings {
+            if let SettingItem::SortByPriority(enabled) = setting {
+                return *enabled;
+            }
+        }
+        true
+    }
+
     pub fn render(&self, frame: &mut Frame, area: Rect, styles: &ThemeStyles, colors: &ThemeColors) {
         if !self.active {
             return;
@@ -0,0 +1,105 @@
+use crate::models::TodoList;
+use crate::ui::styling::ThemeStyles;
+use ratatui::{
+    layout::Rect,
+    text::{Line, Span},
+    widgets::{Block, Borders, List, ListItem, ListState},
+    Frame,
+};
+
+pub struct TagSidebar {
+    pub visible: bool,
+    pub focused: bool,
+    pub state: ListState,
+    pub selected_tag: Option<String>,
+}
+
+impl TagSidebar {
+    pub fn new() -> Self {
+        let mut state = ListState::default();
+        state.select(Some(0));
+        Self {
+            visible: false,
+            focused: false,
+            state,
+            selected_tag: None,
+        }
+    }
+
+    pub fn toggle(&mut self) {
+        self.visible = !self.visible;
+        self.focused = self.visible;
+    }
+
+    fn entries(todos: &TodoList) -> Vec<Option<String>> {
+        let mut entries = vec![None];
+        entries.extend(todos.get_tag_counts().into_iter().map(|(tag, _)| Some(tag)));
+        entries
+    }
+
+    pub fn select_next(&mut self, todos: &TodoList) {
+        let len = Self::entries(todos).len();
+        let selected = self.state.selected().unwrap_or(0);
+        self.state.select(Some(if selected + 1 >= len { 0 } else { selected + 1 }));
+    }
+
+    pub fn select_previous(&mut self, todos: &TodoList) {
+        let len = Self::entries(todos).len();
+        let selected = self.state.selected().unwrap_or(0);
+        self.state.select(Some(if selected == 0 { len - 1 } else { selected - 1 }));
+    }
+
+    pub fn apply_selection(&mut self, todos: &TodoList) -> Option<String> {
+        let selected = self.state.selected().unwrap_or(0);
+        self.selected_tag = Self::entries(todos).get(selected).cloned().flatten();
+        self.selected_tag.clone()
+    }
+
+    pub fn validate_selection(&mut self, todos: &TodoList) {
+        let entries = Self::entries(todos);
+        if let Some(tag) = &self.selected_tag {
+            match entries.iter().position(|entry| entry.as_ref() == Some(tag)) {
+                Some(index) => self.state.select(Some(index)),
+                None => {
+                    self.selected_tag = None;
+                    self.state.select(Some(0));
+                }
+            }
+        } else if self.state.selected().unwrap_or(0) >= entries.len() {
+            self.state.select(Some(0));
+        }
+    }
+
+    pub fn render(&mut self, frame: &mut Frame, area: Rect, todos: &TodoList, styles: &ThemeStyles) {
+        let tag_counts = todos.get_tag_counts();
+
+        let mut items = vec![ListItem::new(Line::from(vec![
+            Span::styled(if self.selected_tag.is_none() { "● " } else { "  " }, styles.accent),
+            Span::styled(format!("All ({})", todos.items.len()), styles.normal),
+        ]))];
+
+        for (tag, count) in &tag_counts {
+            let marker = if self.selected_tag.as_ref() == Some(tag) { "● " } else { "  " };
+            items.push(ListItem::new(Line::from(vec![
+                Span::styled(marker, styles.accent),
+                Span::styled(format!("#{}", tag), styles.accent),
+                Span::styled(format!(" ({})", count), styles.muted),
+            ])));
+        }
+
+        let title = if self.focused { "► Tags" } else { "Tags" };
+        let border_style = if self.focused { styles.title } else { styles.border };
+
+        let list = List::new(items)
+            .block(
+                Block::default()
+                    .title(title)
+                    .borders(Borders::ALL)
+                    .style(border_style),
+            )
+            .style(styles.normal)
+            .highlight_style(if self.focused { styles.selected } else { styles.normal });
+
+        frame.render_stateful_widget(list, area, &mut self.state);
+    }
+}
@@ -2,7 +2,7 @@ use crate::ui::styling::{ThemeStyles, ThemeColors};
 use ratatui::{
     layout::{Alignment, Rect},
     style::Style,
-    text::Text,
+    text::{Line, Text},
     widgets::{Block, BorderType, Borders, Clear, Paragraph, Wrap},
     Frame,
 };
@@ -22,6 +22,7 @@ pub struct Toast {
     pub level: ToastLevel,
     pub created_at: Instant,
     pub duration: Duration,
+    pub hint: Option<String>,
     pub id: usize,
 }
 
@@ -32,6 +33,7 @@ impl Toast {
             level,
             created_at: Instant::now(),
             duration: Duration::from_secs(3),
+            hint: None,
             id: 0,
         }
     }
@@ -52,6 +54,12 @@ impl Toast {
         Self::new(message, ToastLevel::Error)
     }
 
+    pub fn with_undo_hint(mut self) -> Self {
+        self.hint = Some("u: Undo".to_string());
+        self.duration = Duration::from_secs(5);
+        self
+    }
+
     pub fn is_expired(&self) -> bool {
         self.created_at.elapsed() >= self.duration
     }
@@ -109,6 +117,14 @@ impl ToastManager {
         self.add_toast(Toast::error(message));
     }
 
+    pub fn success_with_undo(&mut self, message: String) {
+        self.add_toast(Toast::success(message).with_undo_hint());
+    }
+
+    pub fn error_with_undo(&mut self, message: String) {
+        self.add_toast(Toast::error(message).with_undo_hint());
+    }
+
     pub fn update(&mut self) {
         self.toasts.retain(|toast| !toast.is_expired());
     }
@@ -158,12 +174,16 @@ impl ToastManager {
                 border_style
             };
 
-            let block = Block::default()
+            let mut block = Block::default()
                 .borders(Borders::ALL)
                 .border_type(BorderType::Rounded)
                 .border_style(adjusted_style)
                 .style(Style::default().fg(colors.foreground).bg(colors.modal_bg));
 
+            if let Some(hint) = &toast.hint {
+                block = block.title_bottom(Line::from(format!(" {} ", hint)).style(styles.muted).right_aligned());
+            }
+
             let content = format!("{} {}", icon, toast.message);
             let paragraph = Paragraph::new(Text::from(content))
                 .style(text_style)
@@ -1,21 +1,36 @@
-use crate::models::{TodoItem, TodoList, format_datetime};
+use crate::models::{TodoItem, TodoList, format_datetime, format_due_date, format_duration, format_relative_due};
 use crate::models::config::DateFormat;
 use crate::ui::styling::ThemeStyles;
+use std::collections::{HashMap, HashSet};
+use uuid::Uuid;
 use ratatui::{
-    layout::Rect,
+    layout::{Margin, Position, Rect},
     text::{Line, Span, Text},
     widgets::{Block, Borders, List, ListItem, ListState},
     Frame,
 };
 
+type Children<'a> = HashMap<Uuid, Vec<&'a TodoItem>>;
+
 #[derive(Clone)]
 pub struct TodoListComponent {
     pub state: ListState,
     pub show_completed: bool,
     pub expand_all: bool,
+    pub sort_by_priority: bool,
+    pub tag_filter: Option<String>,
+    pub status_filter: Option<String>,
+    pub area: Rect,
+    row_heights: Vec<u16>,
 }
 
 impl TodoListComponent {
+    pub fn new_board_column(status: &str) -> Self {
+        let mut column = Self::new();
+        column.status_filter = Some(status.to_string());
+        column
+    }
+
     pub fn new() -> Self {
         let mut state = ListState::default();
         state.select(Some(0));
@@ -23,20 +38,77 @@ impl TodoListComponent {
             state,
             show_completed: false,
             expand_all: false,
+            sort_by_priority: true,
+            tag_filter: None,
+            status_filter: None,
+            area: Rect::default(),
+            row_heights: Vec::new(),
         }
     }
 
+    pub fn visible_items<'a>(&self, todos: &'a TodoList) -> Vec<&'a TodoItem> {
+        self.visible_rows(todos).into_iter().map(|(item, _)| item).collect()
+    }
+
+    pub fn visible_rows<'a>(&self, todos: &'a TodoList) -> Vec<(&'a TodoItem, usize)> {
+        self.tree_rows(todos).0
+    }
+
+    /// The visible rows with their depth, plus each todo's children within this pane.
+    fn tree_rows<'a>(&self, todos: &'a TodoList) -> (Vec<(&'a TodoItem, usize)>, Children<'a>) {
+        let items = self.pane_items(todos);
+        let ids: HashSet<Uuid> = items.iter().map(|item| item.id).collect();
+
+        let mut roots = Vec::new();
+        let mut children: Children<'a> = HashMap::new();
+        for item in &items {
+            match item.parent_id.filter(|parent_id| ids.contains(parent_id)) {
+                Some(parent_id) => children.entry(parent_id).or_default().push(item),
+                None => roots.push(*item),
+            }
+        }
+
+        let mut rows = Vec::with_capacity(items.len());
+        for root in roots {
+            self.push_tree_rows(root, 0, &children, &mut rows);
+        }
+        (rows, children)
+    }
+
+    fn push_tree_rows<'a>(&self, item: &'a TodoItem, depth: usize, children: &Children<'a>, rows: &mut Vec<(&'a TodoItem, usize)>) {
+        rows.push((item, depth));
+        if item.expanded || self.expand_all {
+            for child in children.get(&item.id).into_iter().flatten() {
+                self.push_tree_rows(child, depth + 1, children, rows);
+            }
+        }
+    }
+
+    fn pane_items<'a>(&self, todos: &'a TodoList) -> Vec<&'a TodoItem> {
+        let mut items = match &self.status_filter {
+            Some(status) => todos.get_todos_by_status(status),
+            None if self.show_completed => todos.get_completed_todos(),
+            None => todos.get_active_todos(),
+        };
+
+        if let Some(tag) = &self.tag_filter {
+            items.retain(|item| item.has_tag(tag));
+        }
+
+        if self.sort_by_priority {
+            items.sort_by_key(|item| std::cmp::Reverse(item.priority));
+        }
+
+        items
+    }
+
     pub fn toggle_view(&mut self) {
         self.show_completed = !self.show_completed;
         self.state.select(Some(0));
     }
 
     pub fn select_next(&mut self, todos: &TodoList) {
-        let items = if self.show_completed {
-            todos.get_completed_todos()
-        } else {
-            todos.get_active_todos()
-        };
+        let items = self.visible_items(todos);
 
         if items.is_empty() {
             self.state.select(None);
@@ -53,11 +125,7 @@ impl TodoListComponent {
     }
 
     pub fn select_previous(&mut self, todos: &TodoList) {
-        let items = if self.show_completed {
-            todos.get_completed_todos()
-        } else {
-            todos.get_active_todos()
-        };
+        let items = self.visible_items(todos);
 
         if items.is_empty() {
             self.state.select(None);
@@ -75,12 +143,52 @@ impl TodoListComponent {
 
     pub fn get_selected_todo<'a>(&self, todos: &'a TodoList) -> Option<&'a TodoItem> {
         let selected = self.state.selected()?;
-        let items = if self.show_completed {
-            todos.get_completed_todos()
-        } else {
-            todos.get_active_todos()
-        };
-        items.get(selected).copied()
+        self.visible_items(todos).get(selected).copied()
+    }
+
+    pub fn get_previous_sibling(&self, todos: &TodoList, id: &Uuid) -> Option<Uuid> {
+        let rows = self.visible_rows(todos);
+        let index = rows.iter().position(|(item, _)| &item.id == id)?;
+        let (item, depth) = rows[index];
+        rows[..index]
+            .iter()
+            .rev()
+            .take_while(|(_, row_depth)| *row_depth >= depth)
+            .find(|(row, row_depth)| *row_depth == depth && row.parent_id == item.parent_id)
+            .map(|(row, _)| row.id)
+    }
+
+    pub fn get_next_sibling(&self, todos: &TodoList, id: &Uuid) -> Option<Uuid> {
+        let rows = self.visible_rows(todos);
+        let index = rows.iter().position(|(item, _)| &item.id == id)?;
+        let (item, depth) = rows[index];
+        rows[index + 1..]
+            .iter()
+            .take_while(|(_, row_depth)| *row_depth >= depth)
+            .find(|(row, row_depth)| *row_depth == depth && row.parent_id == item.parent_id)
+            .map(|(row, _)| row.id)
+    }
+
+    pub fn row_at(&self, column: u16, row: u16) -> Option<usize> {
+        let inner = self.area.inner(Margin { horizontal: 1, vertical: 1 });
+        if !inner.contains(Position { x: column, y: row }) {
+            return None;
+        }
+
+        let mut top = inner.y;
+        for (index, height) in self.row_heights.iter().enumerate().skip(self.state.offset()) {
+            if row < top + height {
+                return Some(index);
+            }
+            top += height;
+        }
+        None
+    }
+
+    pub fn select_by_id(&mut self, todos: &TodoList, id: &Uuid) {
+        if let Some(index) = self.visible_items(todos).iter().position(|item| &item.id == id) {
+            self.state.select(Some(index));
+        }
     }
 
     pub fn expand_all(&mut self) {
@@ -96,11 +204,7 @@ impl TodoListComponent {
     }
 
     pub fn validate_selection(&mut self, todos: &TodoList) {
-        let items = if self.show_completed {
-            todos.get_completed_todos()
-        } else {
-            todos.get_active_todos()
-        };
+        let items = self.visible_items(todos);
 
         if items.is_empty() {
             self.state.select(None);
@@ -114,11 +218,9 @@ impl TodoListComponent {
     }
 
     pub fn render(&mut self, frame: &mut Frame, area: Rect, todos: &TodoList, styles: &ThemeStyles, is_active: bool, compact_mode: bool, date_format: &DateFormat) {
-        let items = if self.show_completed {
-            todos.get_completed_todos()
-        } else {
-            todos.get_active_todos()
-        };
+        let (rows, pane_children) = self.tree_rows(todos);
+        let progress = todos.child_progress();
+        let items: Vec<&TodoItem> = rows.iter().map(|(item, _)| *item).collect();
 
         if items.is_empty() {
             self.state.select(None);
@@ -130,20 +232,28 @@ impl TodoListComponent {
             }
         }
 
-        let list_items: Vec<ListItem> = items
+        let list_items: Vec<ListItem> = rows
             .iter()
-            .enumerate()
-            .map(|(_index, todo)| {
+            .map(|(todo, depth)| {
                 let mut lines = vec![];
 
                 if !compact_mode {
                     lines.push(Line::from(""));
                 }
 
-                let mut spans = vec![];
+                let indent = "  ".repeat(*depth);
+                let mut spans = vec![Span::raw(indent.clone())];
 
-                if todo.completed {
+                if pane_children.contains_key(&todo.id) {
+                    let expanded = todo.expanded || self.expand_all;
+                    spans.push(Span::styled(if expanded { "▾ " } else { "▸ " }, styles.accent));
+                }
+
+                if todo.is_completed() {
                     spans.push(Span::styled("✓ ", styles.completed));
+                    if !todo.priority.is_none() {
+                        spans.push(Span::styled(todo.priority.marker(), styles.completed));
+                    }
                     spans.push(Span::styled(&todo.title, styles.completed));
 
                     if let Some(completed_at) = todo.completed_at {
@@ -155,7 +265,49 @@ impl TodoListComponent {
                     }
                 } else {
                     spans.push(Span::styled("○ ", styles.normal));
-                    spans.push(Span::styled(&todo.title, styles.title));
+                    if !todo.priority.is_none() {
+                        spans.push(Span::styled(todo.priority.marker(), styles.priority(&todo.priority)));
+                    }
+                    let title_style = if todo.is_overdue() { styles.overdue } else { styles.title };
+                    spans.push(Span::styled(&todo.title, title_style));
+
+                    if let Some(due_at) = &todo.due_at {
+                        let relative = format_relative_due(due_at);
+                        let due_text = if *date_format == DateFormat::Relative {
+                            if todo.is_overdue() {
+                                format!(" ({})", relative)
+                            } else {
+                                format!(" (due {})", relative)
+                            }
+                        } else {
+                            format!(" (due {}, {})", format_due_date(due_at, date_format), relative)
+                        };
+                        spans.push(Span::styled(
+                            due_text,
+                            if todo.is_overdue() { styles.overdue } else { styles.muted }
+                        ));
+                    }
+
+                    if let Some(recurrence) = &todo.recurrence {
+                        spans.push(Span::styled(format!(" ↻ {}", recurrence.describe()), styles.accent));
+                    }
+
+                    if self.status_filter.is_none() && todo.status != todos.initial_state() {
+                        spans.push(Span::styled(format!(" [{}]", todo.status), styles.accent));
+                    }
+                }
+
+                if let Some((completed, total)) = progress.get(&todo.id) {
+                    spans.push(Span::styled(format!(" [{}/{}]", completed, total), styles.accent));
+                }
+
+                for tag in &todo.tags {
+                    spans.push(Span::styled(format!(" #{}", tag), styles.accent));
+                }
+
+                if !todo.time_entries.is_empty() {
+                    let time_text = format!(" ⏱ {}", format_duration(&todo.total_time_spent()));
+                    spans.push(Span::styled(time_text, if todo.is_timer_running() { styles.timer } else { styles.muted }));
                 }
 
                 if todo.has_description() {
@@ -177,7 +329,7 @@ impl TodoListComponent {
                             .map(|line| {
                                 if line.trim_start().starts_with("- ") {
                                     Line::from(vec![
-                                        Span::styled("  ", styles.description),
+                                        Span::styled(format!("{}  ", indent), styles.description),
                                         Span::styled("• ", styles.accent),
                                         Span::styled(
                                             line.trim_start().strip_prefix("- ").unwrap_or(line),
@@ -186,7 +338,7 @@ impl TodoListComponent {
                                     ])
                                 } else if line.trim_start().starts_with("* ") {
                                     Line::from(vec![
-                                        Span::styled("  ", styles.description),
+                                        Span::styled(format!("{}  ", indent), styles.description),
                                         Span::styled("• ", styles.accent),
                                         Span::styled(
                                             line.trim_start().strip_prefix("* ").unwrap_or(line),
@@ -195,7 +347,7 @@ impl TodoListComponent {
                                     ])
                                 } else {
                                     Line::from(Span::styled(
-                                        format!("  {}", line),
+                                        format!("{}  {}", indent, line),
                                         styles.description,
                                     ))
                                 }
@@ -212,7 +364,16 @@ impl TodoListComponent {
             })
             .collect();
 
-        let title = if self.show_completed {
+        self.area = area;
+        self.row_heights = list_items.iter().map(|item| item.height() as u16).collect();
+
+        let mut title = if let Some(status) = &self.status_filter {
+            if is_active {
+                format!("► {} ({})", status, items.len())
+            } else {
+                format!("{} ({})", status, items.len())
+            }
+        } else if self.show_completed {
             if is_active {
                 format!("► Completed Todos ({})", items.len())
             } else {
@@ -226,6 +387,10 @@ impl TodoListComponent {
             }
         };
 
+        if let Some(tag) = &self.tag_filter {
+            title.push_str(&format!(" #{}", tag));
+        }
+
         let border_style = if is_active {
             styles.title
         } else {
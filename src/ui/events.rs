@@ -31,6 +31,20 @@ pub enum AppEvent {
     Save,
     ShowHelp,
     ToggleZoom,
+    RaisePriority,
+    LowerPriority,
+    ToggleTagSidebar,
+    IndentTodo,
+    OutdentTodo,
+    AddSubtask,
+    MoveTodoUp,
+    MoveTodoDown,
+    ToggleTimer,
+    ToggleBoardView,
+    MoveStatusBackward,
+    MoveStatusForward,
+    Undo,
+    Redo,
     Char(char),
 }
 
@@ -1,5 +1,5 @@
-use crate::models::ColorTheme;
-use ratatui::style::{Color, Style};
+use crate::models::{ColorTheme, Priority};
+use ratatui::style::{Color, Modifier, Style};
 
 pub struct ThemeColors {
     pub background: Color,
@@ -223,11 +223,18 @@ pub struct ThemeStyles {
     pub help_text: Style,
     pub accent: Style,
     pub muted: Style,
+    pub priority_low: Style,
+    pub priority_medium: Style,
+    pub priority_high: Style,
+    pub priority_urgent: Style,
+    pub overdue: Style,
+    pub timer: Style,
     pub cursor_fg: Color,
     pub cursor_bg: Color,
 }
 
 impl ThemeStyles {
+
     pub fn from_colors(colors: &ThemeColors) -> Self {
         Self {
             normal: Style::default().fg(colors.foreground).bg(colors.background),
@@ -240,8 +247,24 @@ impl ThemeStyles {
             help_text: Style::default().fg(colors.muted),
             accent: Style::default().fg(colors.accent),
             muted: Style::default().fg(colors.muted),
+            priority_low: Style::default().fg(colors.muted),
+            priority_medium: Style::default().fg(colors.primary),
+            priority_high: Style::default().fg(colors.warning),
+            priority_urgent: Style::default().fg(colors.error).add_modifier(Modifier::BOLD),
+            overdue: Style::default().fg(colors.error),
+            timer: Style::default().fg(colors.success).add_modifier(Modifier::BOLD),
             cursor_fg: colors.vim_text,
             cursor_bg: colors.primary,
         }
     }
-}
\ No newline at end of file
+
+    pub fn priority(&self, priority: &Priority) -> Style {
+        match priority {
+            Priority::None => self.normal,
+            Priority::Low => self.priority_low,
+            Priority::Medium => self.priority_medium,
+            Priority::High => self.priority_high,
+            Priority::Urgent => self.priority_urgent,
+        }
+    }
+}